@@ -67,6 +67,8 @@ impl Named for GreeterBot {
 }
 
 impl Greeter for GreeterBot {
+    // Deliberately doesn't mention the bot's own name — that's `interact`'s job
+    // (via `Named::name`), so `greet` alone stays a plain, nameless greeting.
     fn greet(&self, name: &str) -> String {
         format!("Greetings, {}!", name)
     }
@@ -76,6 +78,222 @@ impl Interactive for GreeterBot {}
 
 impl Displayable for GreeterBot {}
 
+pub struct TemplateGreeter {
+    pub template: String,
+}
+
+impl Greeter for TemplateGreeter {
+    fn greet(&self, name: &str) -> String {
+        expand_template(&self.template, name)
+    }
+}
+
+// `{{`/`}}` collapse to a literal brace; an unrecognized `{token}` is left as-is
+// (braces included) rather than dropped, so a typo in a template is visible
+// instead of silently eating text.
+fn expand_template(template: &str, name: &str) -> String {
+    let mut out = String::with_capacity(template.len());
+    let mut chars = template.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '{' if chars.peek() == Some(&'{') => {
+                chars.next();
+                out.push('{');
+            }
+            '{' => {
+                let mut token = String::new();
+                let mut closed = false;
+                for c2 in chars.by_ref() {
+                    if c2 == '}' {
+                        closed = true;
+                        break;
+                    }
+                    token.push(c2);
+                }
+
+                if !closed {
+                    out.push('{');
+                    out.push_str(&token);
+                    continue;
+                }
+
+                match token.as_str() {
+                    "name" => out.push_str(name),
+                    "host" => out.push_str(&hostname()),
+                    "os" => out.push_str(std::env::consts::OS),
+                    "time" => out.push_str(&current_time_string()),
+                    _ => {
+                        out.push('{');
+                        out.push_str(&token);
+                        out.push('}');
+                    }
+                }
+            }
+            '}' if chars.peek() == Some(&'}') => {
+                chars.next();
+                out.push('}');
+            }
+            _ => out.push(c),
+        }
+    }
+
+    out
+}
+
+fn hostname() -> String {
+    std::env::var("HOSTNAME")
+        .or_else(|_| std::env::var("COMPUTERNAME"))
+        .unwrap_or_else(|_| "unknown".to_string())
+}
+
+fn seconds_of_day() -> u64 {
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+        % 86400
+}
+
+fn current_time_string() -> String {
+    let secs_of_day = seconds_of_day();
+    format!("{:02}:{:02}:{:02}", secs_of_day / 3600, (secs_of_day % 3600) / 60, secs_of_day % 60)
+}
+
+fn current_hour() -> u32 {
+    (seconds_of_day() / 3600) as u32
+}
+
+// Translates literal escape markers (as they'd appear typed in a config file or
+// template string) into real ANSI escape bytes, so `\e[`, `\033[`, and `\x1b[`
+// all produce the same `\x1b[` a terminal expects.
+pub fn colorize(text: &str) -> String {
+    text.replace("\\e[", "\x1b[")
+        .replace("\\033[", "\x1b[")
+        .replace("\\x1b[", "\x1b[")
+}
+
+pub struct ConfigurableGreeter {
+    greeting: String,
+}
+
+impl ConfigurableGreeter {
+    pub fn new(greeting: &str) -> Self {
+        Self {
+            greeting: greeting.to_string(),
+        }
+    }
+
+    pub fn from_file(path: &std::path::Path) -> std::io::Result<Self> {
+        let greeting = std::fs::read_to_string(path)?;
+        Ok(Self::new(greeting.trim()))
+    }
+}
+
+impl Greeter for ConfigurableGreeter {
+    fn greet(&self, name: &str) -> String {
+        format!("{} {}!", self.greeting, name)
+    }
+}
+
+// No `chrono` dependency is available in this crate, so the salutation is keyed
+// off UTC wall-clock time and "locale" only switches between a couple of
+// hardcoded salutation word sets rather than full locale-aware formatting.
+pub struct TimeGreeter {
+    pub morning_cutoff: u32,
+    pub afternoon_cutoff: u32,
+    pub evening_cutoff: u32,
+    pub locale: String,
+    pub include_timestamp: bool,
+}
+
+impl TimeGreeter {
+    pub fn new(locale: &str) -> Self {
+        Self {
+            morning_cutoff: 12,
+            afternoon_cutoff: 17,
+            evening_cutoff: 21,
+            locale: locale.to_string(),
+            include_timestamp: false,
+        }
+    }
+
+    fn salutation(&self, hour: u32) -> &'static str {
+        let is_french = self.locale.starts_with("fr");
+        if hour < self.morning_cutoff {
+            if is_french {
+                "Bonjour"
+            } else {
+                "Good morning"
+            }
+        } else if hour < self.afternoon_cutoff {
+            if is_french {
+                "Bon après-midi"
+            } else {
+                "Good afternoon"
+            }
+        } else if hour < self.evening_cutoff {
+            if is_french {
+                "Bonsoir"
+            } else {
+                "Good evening"
+            }
+        } else if is_french {
+            "Bonne nuit"
+        } else {
+            "Good night"
+        }
+    }
+}
+
+impl Greeter for TimeGreeter {
+    fn greet(&self, name: &str) -> String {
+        let salutation = self.salutation(current_hour());
+        if self.include_timestamp {
+            format!("{}, {}! ({})", salutation, name, current_time_string())
+        } else {
+            format!("{}, {}!", salutation, name)
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, clap::ValueEnum)]
+pub enum GreeterKind {
+    Friendly,
+    Formal,
+    Bot,
+}
+
+pub fn make_greeter(kind: GreeterKind, bot_name: &str) -> Box<dyn Greeter> {
+    match kind {
+        GreeterKind::Friendly => Box::new(FriendlyGreeter),
+        GreeterKind::Formal => Box::new(FormalGreeter),
+        GreeterKind::Bot => Box::new(GreeterBot::new(bot_name)),
+    }
+}
+
+pub fn strip_ansi(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut chars = text.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if c == '\x1b' && chars.peek() == Some(&'[') {
+            chars.next();
+            for c2 in chars.by_ref() {
+                if c2.is_ascii_alphabetic() {
+                    break;
+                }
+            }
+        } else {
+            out.push(c);
+        }
+    }
+
+    out
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -104,4 +322,124 @@ mod tests {
         assert!(bot.greet("Alice").contains("Greetings"));
         assert!(bot.interact("Bob").contains("I am R2D2"));
     }
+
+    #[test]
+    fn test_template_greeter_substitutes_name() {
+        let greeter = TemplateGreeter {
+            template: "Hi {name}!".to_string(),
+        };
+        assert_eq!(greeter.greet("Alice"), "Hi Alice!");
+    }
+
+    #[test]
+    fn test_template_greeter_leaves_unknown_token_literal() {
+        let greeter = TemplateGreeter {
+            template: "{name} says {blorp}".to_string(),
+        };
+        assert_eq!(greeter.greet("Bob"), "Bob says {blorp}");
+    }
+
+    #[test]
+    fn test_template_greeter_escapes_braces() {
+        let greeter = TemplateGreeter {
+            template: "{{literal}} {name}".to_string(),
+        };
+        assert_eq!(greeter.greet("Eve"), "{literal} Eve");
+    }
+
+    #[test]
+    fn test_template_greeter_expands_os_and_host() {
+        let greeter = TemplateGreeter {
+            template: "{os} {host}".to_string(),
+        };
+        let out = greeter.greet("Alice");
+        assert!(out.contains(std::env::consts::OS));
+    }
+
+    #[test]
+    fn test_colorize_translates_escape_markers() {
+        assert_eq!(colorize("\\e[31mHi\\e[0m"), "\x1b[31mHi\x1b[0m");
+        assert_eq!(colorize("\\033[1m"), "\x1b[1m");
+        assert_eq!(colorize("\\x1b[1m"), "\x1b[1m");
+    }
+
+    #[test]
+    fn test_strip_ansi_removes_escape_sequences() {
+        assert_eq!(strip_ansi("\x1b[31mHi\x1b[0m"), "Hi");
+        assert_eq!(strip_ansi("plain"), "plain");
+    }
+
+    #[test]
+    fn test_configurable_greeter_new() {
+        let greeter = ConfigurableGreeter::new("Bonjour");
+        assert_eq!(greeter.greet("Alice"), "Bonjour Alice!");
+    }
+
+    #[test]
+    fn test_configurable_greeter_from_file() {
+        let path = std::env::temp_dir().join(format!(
+            "rust-template-greeting-{:?}.txt",
+            std::thread::current().id()
+        ));
+        std::fs::write(&path, "Howdy\n").unwrap();
+
+        let greeter = ConfigurableGreeter::from_file(&path).unwrap();
+        assert_eq!(greeter.greet("Bob"), "Howdy Bob!");
+
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_configurable_greeter_from_file_missing_errors() {
+        let path = std::path::Path::new("/nonexistent/greeting.txt");
+        assert!(ConfigurableGreeter::from_file(path).is_err());
+    }
+
+    #[test]
+    fn test_time_greeter_salutation_cutoffs() {
+        let greeter = TimeGreeter::new("en");
+        assert_eq!(greeter.salutation(6), "Good morning");
+        assert_eq!(greeter.salutation(13), "Good afternoon");
+        assert_eq!(greeter.salutation(18), "Good evening");
+        assert_eq!(greeter.salutation(22), "Good night");
+    }
+
+    #[test]
+    fn test_time_greeter_locale_fr() {
+        let greeter = TimeGreeter::new("fr");
+        assert_eq!(greeter.salutation(6), "Bonjour");
+    }
+
+    #[test]
+    fn test_time_greeter_greet_includes_name() {
+        let greeter = TimeGreeter::new("en");
+        assert!(greeter.greet("Alice").contains("Alice"));
+    }
+
+    #[test]
+    fn test_time_greeter_greet_with_timestamp() {
+        let mut greeter = TimeGreeter::new("en");
+        greeter.include_timestamp = true;
+        let out = greeter.greet("Bob");
+        assert!(out.contains("Bob"));
+        assert!(out.contains(':'));
+    }
+
+    #[test]
+    fn test_make_greeter_friendly() {
+        let greeter = make_greeter(GreeterKind::Friendly, "Bot");
+        assert_eq!(greeter.greet("World"), "Hello, World!");
+    }
+
+    #[test]
+    fn test_make_greeter_formal() {
+        let greeter = make_greeter(GreeterKind::Formal, "Bot");
+        assert_eq!(greeter.greet("World"), "Good day, World.");
+    }
+
+    #[test]
+    fn test_make_greeter_bot() {
+        let greeter = make_greeter(GreeterKind::Bot, "R2D2");
+        assert!(greeter.greet("Alice").contains("Greetings"));
+    }
 }
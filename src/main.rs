@@ -1,5 +1,9 @@
 use clap::Parser;
-use rust_template::greet;
+use rust_template::{
+    colorize, make_greeter, strip_ansi, ConfigurableGreeter, Greeter, GreeterBot, GreeterKind, TimeGreeter,
+};
+use std::io::IsTerminal;
+use std::path::PathBuf;
 
 #[derive(Parser)]
 #[command(name = "rust-template")]
@@ -8,23 +12,152 @@ use rust_template::greet;
 struct Cli {
     #[arg(short, long, default_value = "World")]
     name: String,
+
+    #[arg(long, conflicts_with = "style")]
+    template: Option<String>,
+
+    #[arg(long)]
+    no_color: bool,
+
+    #[arg(long)]
+    greeting_file: Option<PathBuf>,
+
+    #[arg(long)]
+    locale: Option<String>,
+
+    #[arg(long, value_enum, default_value_t = GreeterKind::Friendly)]
+    style: GreeterKind,
+
+    #[arg(long, default_value = "GreeterBot")]
+    bot_name: String,
 }
 
 fn main() {
     let cli = Cli::parse();
-    println!("{}", greet(&cli.name));
+
+    let greeting = render_greeting(&cli);
+
+    let greeting = colorize(&greeting);
+    let greeting = if cli.no_color || !std::io::stdout().is_terminal() {
+        strip_ansi(&greeting)
+    } else {
+        greeting
+    };
+
+    println!("{}", greeting);
+}
+
+/// Pick which greeter renders `cli.name`'s greeting, by the same precedence `main`
+/// dispatches on: an explicit `--greeting-file` wins over `--template`, which wins
+/// over `--locale`, which wins over `--style`/`--bot-name` (clap's own
+/// `conflicts_with` keeps `--template` and `--style` from being set together, so
+/// only one of those two branches can ever be reachable at a time).
+///
+/// `GreeterKind::Bot` is special-cased to `GreeterBot::process_greeting` rather than
+/// going through `make_greeter`'s `Box<dyn Greeter>::greet()`: `GreeterBot::greet`
+/// deliberately omits the bot's own name (see the comment on that impl), so reaching it
+/// through the trait object would make `--bot-name` a silent no-op.
+fn render_greeting(cli: &Cli) -> String {
+    if let Some(path) = &cli.greeting_file {
+        let greeter = ConfigurableGreeter::from_file(path).unwrap_or_else(|err| {
+            eprintln!("error: failed to read greeting file {}: {err}", path.display());
+            std::process::exit(1);
+        });
+        greeter.greet(&cli.name)
+    } else if let Some(template) = &cli.template {
+        let greeter = rust_template::TemplateGreeter {
+            template: template.clone(),
+        };
+        greeter.greet(&cli.name)
+    } else if let Some(locale) = &cli.locale {
+        TimeGreeter::new(locale).greet(&cli.name)
+    } else if cli.style == GreeterKind::Bot {
+        GreeterBot::new(&cli.bot_name).process_greeting(&cli.name)
+    } else {
+        let greeter = make_greeter(cli.style, &cli.bot_name);
+        greeter.greet(&cli.name)
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    fn parse(args: &[&str]) -> Cli {
+        let mut argv = vec!["rust-template"];
+        argv.extend_from_slice(args);
+        Cli::try_parse_from(argv).expect("argv should parse")
+    }
+
     #[test]
-    fn test_cli_parsing() {
-        let cli = Cli {
-            name: "Test".to_string(),
-        };
+    fn test_cli_parsing_defaults_name_and_style() {
+        let cli = parse(&[]);
+
+        assert_eq!(cli.name, "World");
+        assert_eq!(cli.style, GreeterKind::Friendly);
+        assert_eq!(cli.bot_name, "GreeterBot");
+    }
+
+    #[test]
+    fn test_cli_parsing_rejects_template_and_style_together() {
+        let result = Cli::try_parse_from(["rust-template", "--template", "Hi {name}!", "--style", "bot"]);
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_render_greeting_uses_template_over_style() {
+        let cli = parse(&["--name", "Ada", "--template", "Hi {name}!"]);
+
+        assert_eq!(render_greeting(&cli), "Hi Ada!");
+    }
+
+    #[test]
+    fn test_cli_parsing_no_color_flag() {
+        let cli = parse(&["--no-color"]);
+
+        assert!(cli.no_color);
+    }
+
+    #[test]
+    fn test_render_greeting_greeting_file_takes_precedence_over_template() {
+        let path = std::env::temp_dir().join(format!(
+            "rust-template-main-test-{:?}.txt",
+            std::thread::current().id()
+        ));
+        std::fs::write(&path, "Howdy").unwrap();
+
+        let cli = parse(&[
+            "--name",
+            "Bob",
+            "--greeting-file",
+            path.to_str().unwrap(),
+            "--template",
+            "Hi {name}!",
+        ]);
+
+        assert_eq!(render_greeting(&cli), "Howdy Bob!");
+
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_render_greeting_dispatches_locale_to_time_greeter() {
+        let cli = parse(&["--name", "Claire", "--locale", "fr"]);
+
+        let greeting = render_greeting(&cli);
+        let is_french_salutation = ["Bonjour", "Bon après-midi", "Bonsoir", "Bonne nuit"]
+            .iter()
+            .any(|salutation| greeting.starts_with(salutation));
+
+        assert!(is_french_salutation, "unexpected greeting: {greeting}");
+        assert!(greeting.ends_with("Claire!"));
+    }
+
+    #[test]
+    fn test_render_greeting_dispatches_style_and_bot_name() {
+        let cli = parse(&["--name", "Alice", "--style", "bot", "--bot-name", "R2D2"]);
 
-        assert_eq!(cli.name, "Test");
+        assert!(render_greeting(&cli).contains("R2D2"));
     }
 }
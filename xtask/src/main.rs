@@ -1,18 +1,70 @@
 mod relationships;
+mod svg_doc;
+mod verus_contracts;
 
+use std::collections::HashMap;
 use std::env;
 use std::fs;
 use std::path::{Path, PathBuf};
 use std::process::Command;
+
+use rayon::prelude::*;
 use walkdir::WalkDir;
 
-use base64::{Engine as _, engine::general_purpose};
 use relationships::{
-    CodeRelationships, extract_relationships, generate_function_call_graph,
-    generate_type_inheritance_graph,
+    CodeRelationships, GraphOptions, export_relationships_json, extract_relationships,
+    find_unreachable_functions, generate_function_call_graph, generate_function_call_graph_dot,
+    generate_function_cfg_graph, generate_index, generate_reachability_graph,
+    generate_type_inheritance_graph, generate_type_inheritance_graph_cfg,
+    generate_type_inheritance_graph_dot, import_relationships_json,
 };
+use verus_contracts::VerusContract;
+
+// `Send + Sync` so errors from the parallel HTML-processing pass can cross thread
+// boundaries.
+type Result<T> = std::result::Result<T, Box<dyn std::error::Error + Send + Sync>>;
+
+/// The single item a generated doc page documents, keyed by the page's own filename
+/// (e.g. `fn.greet.html`, `struct.GreeterBot.html`). Built once per `doc` run so each
+/// HTML file only has to generate and inject the one graph it actually needs, instead
+/// of scanning every function and type in the crate.
+enum DocPageItem {
+    Function(String),
+    Type(String),
+}
+
+/// Map rustdoc page filenames to the function/type they document.
+fn build_page_index(relationships: &CodeRelationships) -> HashMap<String, DocPageItem> {
+    let mut index = HashMap::new();
+
+    for (name, metadata) in &relationships.functions {
+        // Methods are documented on their parent type's page, not a page of their own.
+        if metadata.is_method {
+            continue;
+        }
+        let simple = name.split("::").last().unwrap_or(name);
+        index.insert(
+            format!("fn.{}.html", simple),
+            DocPageItem::Function(name.clone()),
+        );
+    }
+
+    for info in relationships.inheritance.values() {
+        let simple = info
+            .type_name
+            .split("::")
+            .last()
+            .unwrap_or(&info.type_name);
+        index
+            .entry(format!("struct.{}.html", simple))
+            .or_insert_with(|| DocPageItem::Type(info.type_name.clone()));
+        index
+            .entry(format!("enum.{}.html", simple))
+            .or_insert_with(|| DocPageItem::Type(info.type_name.clone()));
+    }
 
-type Result<T> = std::result::Result<T, Box<dyn std::error::Error>>;
+    index
+}
 
 fn main() -> Result<()> {
     let args: Vec<String> = env::args().collect();
@@ -25,6 +77,18 @@ fn main() -> Result<()> {
     match args[1].as_str() {
         "doc" => generate_and_process_docs(false)?,
         "doc-open" => generate_and_process_docs(true)?,
+        "export-dot" => export_dot(args.get(2).map(String::as_str), args.get(3..).unwrap_or(&[]))?,
+        "cfg-graph" => export_cfg_graph(args.get(2).map(String::as_str), args.get(3..).unwrap_or(&[]))?,
+        "export-json" => export_json(args.get(2).map(String::as_str))?,
+        "import-json" => import_json(args.get(2).map(String::as_str))?,
+        "dead-code" => {
+            let svg_path = if args.get(2).map(String::as_str) == Some("--svg") {
+                args.get(3).map(String::as_str)
+            } else {
+                None
+            };
+            report_dead_code(svg_path)?
+        }
         "help" | "--help" | "-h" => print_help(),
         _ => {
             eprintln!("Unknown command: {}", args[1]);
@@ -43,9 +107,160 @@ fn print_help() {
     println!("    cargo xtask <COMMAND>");
     println!();
     println!("COMMANDS:");
-    println!("    doc         Generate documentation with call graphs");
-    println!("    doc-open    Generate documentation and open in browser");
-    println!("    help        Print this help message");
+    println!("    doc                   Generate documentation with call graphs");
+    println!("    doc-open              Generate documentation and open in browser");
+    println!("    export-dot <name> [cfg...]   Print a function's call graph or a type's");
+    println!("                                 trait hierarchy as Graphviz DOT, restricted");
+    println!("                                 to the given active cfg flags (e.g. \"test\")");
+    println!("    cfg-graph <type> [cfg...]    Print a type's trait hierarchy as SVG, with");
+    println!("                                 impls whose #[cfg(...)] doesn't hold under the");
+    println!("                                 given flags drawn greyed-out instead of hidden");
+    println!("                                 (methods carry their predicate in the hover");
+    println!("                                 tooltip), so both configurations are visible");
+    println!("                                 on one graph");
+    println!("    export-json [path]           Write the crate's relationship snapshot (call");
+    println!("                                 graph, usage graph, inheritance, functions) as");
+    println!("                                 JSON to <path>, or stdout if omitted");
+    println!("    import-json <path>           Load a relationship snapshot written by");
+    println!("                                 export-json and print its summary counts");
+    println!("    dead-code [--svg <path>]     List functions unreachable from pub items,");
+    println!("                                 main, and trait-impl methods, optionally");
+    println!("                                 writing the reachability graph as SVG");
+    println!("    help                         Print this help message");
+}
+
+/// Print `name`'s call graph (if it's a function) or trait-implementation hierarchy
+/// (if it's a type) as Graphviz DOT, so it can be piped through `dot`/`neato`/etc.
+/// `active_cfg` are the cfg flags/key-value pairs (e.g. `"test"`, `"feature=\"foo\""`)
+/// to treat as active when filtering `#[cfg(...)]`-gated items out of the graph.
+fn export_dot(name: Option<&str>, active_cfg: &[String]) -> Result<()> {
+    let Some(name) = name else {
+        eprintln!("Usage: cargo xtask export-dot <function-or-type-name> [cfg...]");
+        std::process::exit(1);
+    };
+
+    let workspace_root = find_workspace_root()?;
+    let source_files = collect_source_files(&workspace_root)?;
+    let relationships = extract_relationships(source_files);
+    let active_cfg: std::collections::HashSet<String> = active_cfg.iter().cloned().collect();
+
+    let dot = generate_function_call_graph_dot(name, &relationships, &active_cfg)
+        .or_else(|| generate_type_inheritance_graph_dot(name, &relationships, &active_cfg));
+
+    match dot {
+        Some(dot) => {
+            print!("{}", dot);
+            Ok(())
+        }
+        None => {
+            eprintln!("No call graph or trait hierarchy found for '{}'", name);
+            std::process::exit(1);
+        }
+    }
+}
+
+/// Print `name`'s trait-implementation hierarchy as SVG, comparing it against
+/// `active_cfg`: impls whose `#[cfg(...)]` predicate doesn't hold under those flags
+/// are drawn greyed-out with the predicate as a label instead of being hidden (a
+/// method's own predicate is shown in its node's hover tooltip), so a type's trait surface under two different configurations (e.g. `test` vs.
+/// release) can be compared on one graph.
+fn export_cfg_graph(name: Option<&str>, active_cfg: &[String]) -> Result<()> {
+    let Some(name) = name else {
+        eprintln!("Usage: cargo xtask cfg-graph <type-name> [cfg...]");
+        std::process::exit(1);
+    };
+
+    let workspace_root = find_workspace_root()?;
+    let source_files = collect_source_files(&workspace_root)?;
+    let relationships = extract_relationships(source_files);
+    let active_cfg: std::collections::HashSet<String> = active_cfg.iter().cloned().collect();
+
+    let svg = generate_type_inheritance_graph_cfg(
+        name,
+        &relationships,
+        &workspace_root,
+        &active_cfg,
+        GraphOptions::default(),
+    );
+
+    match svg {
+        Some(svg) => {
+            print!("{}", svg);
+            Ok(())
+        }
+        None => {
+            eprintln!("No trait hierarchy found for '{}'", name);
+            std::process::exit(1);
+        }
+    }
+}
+
+/// Extract the crate's relationships and write them as JSON, via
+/// `export_relationships_json`, to `path`, or to stdout if `path` is `None` — so the
+/// snapshot can be diffed between revisions or fed into external graph viewers.
+fn export_json(path: Option<&str>) -> Result<()> {
+    let workspace_root = find_workspace_root()?;
+    let source_files = collect_source_files(&workspace_root)?;
+    let relationships = extract_relationships(source_files);
+    let json = export_relationships_json(&relationships);
+
+    match path {
+        Some(path) => {
+            fs::write(path, json)?;
+            println!("Wrote relationship snapshot to {}", path);
+        }
+        None => print!("{}", json),
+    }
+
+    Ok(())
+}
+
+/// Load a relationship snapshot previously written by `export-json` and print the
+/// same summary counts `doc` prints after extracting them live, so a snapshot can be
+/// sanity-checked without re-parsing the crate.
+fn import_json(path: Option<&str>) -> Result<()> {
+    let Some(path) = path else {
+        eprintln!("Usage: cargo xtask import-json <path>");
+        std::process::exit(1);
+    };
+
+    let json = fs::read_to_string(path)?;
+    let relationships = import_relationships_json(&json)?;
+
+    println!("Loaded relationship snapshot from {}", path);
+    println!("  Found {} functions", relationships.functions.len());
+    println!("  Call graph edges: {}", relationships.call_graph.len());
+    println!("  Inheritance entries: {}", relationships.inheritance.len());
+
+    Ok(())
+}
+
+/// List functions `find_unreachable_functions` flags as candidate dead code, and, when
+/// `svg_path` is given, write the whole-program reachability graph there as SVG.
+fn report_dead_code(svg_path: Option<&str>) -> Result<()> {
+    let workspace_root = find_workspace_root()?;
+    let source_files = collect_source_files(&workspace_root)?;
+    let relationships = extract_relationships(source_files);
+
+    let unreachable = find_unreachable_functions(&relationships);
+
+    if unreachable.is_empty() {
+        println!("No unreachable functions found.");
+    } else {
+        println!("Unreachable functions ({}):", unreachable.len());
+        for name in &unreachable {
+            println!("  {}", name);
+        }
+    }
+
+    if let Some(path) = svg_path {
+        if let Some(svg) = generate_reachability_graph(&relationships, &std::collections::HashSet::new()) {
+            fs::write(path, svg)?;
+            println!("\nReachability graph written to {}", path);
+        }
+    }
+
+    Ok(())
 }
 
 fn generate_and_process_docs(open: bool) -> Result<()> {
@@ -86,21 +301,30 @@ fn generate_and_process_docs(open: bool) -> Result<()> {
 
     println!("\nProcessing documentation files...");
 
-    let mut file_count = 0;
-    for entry in WalkDir::new(&doc_dir)
+    // One filename → item lookup, built once, instead of every file scanning every
+    // function and type to figure out which (if any) graph it needs.
+    let page_index = build_page_index(&relationships);
+
+    let html_files: Vec<PathBuf> = WalkDir::new(&doc_dir)
         .follow_links(true)
         .into_iter()
         .filter_map(|e| e.ok())
-    {
-        let path = entry.path();
+        .map(|e| e.into_path())
+        .filter(|path| path.extension().and_then(|s| s.to_str()) == Some("html"))
+        .collect();
 
-        if path.extension().and_then(|s| s.to_str()) == Some("html") {
-            process_html_file(path, &relationships)?;
-            file_count += 1;
-        }
-    }
+    html_files
+        .par_iter()
+        .try_for_each(|path| process_html_file(path, &relationships, &page_index))?;
+
+    println!("\nProcessed {} HTML files", html_files.len());
+
+    println!("\nInjecting relationship data into the search index...");
+    inject_relationship_search_index(&doc_dir, &relationships)?;
+
+    println!("\nWriting crate-wide relationship index...");
+    write_relationship_index(&doc_dir, &relationships)?;
 
-    println!("\nProcessed {} HTML files", file_count);
     let index_path = "target/doc/rust_template/index.html";
     println!("\nDocumentation available at: {}", index_path);
 
@@ -143,7 +367,11 @@ fn collect_source_files(workspace_root: &Path) -> Result<Vec<PathBuf>> {
     Ok(source_files)
 }
 
-fn process_html_file(path: &Path, relationships: &CodeRelationships) -> Result<()> {
+fn process_html_file(
+    path: &Path,
+    relationships: &CodeRelationships,
+    page_index: &HashMap<String, DocPageItem>,
+) -> Result<()> {
     println!("  Processing: {}", path.display());
 
     // Read the HTML file
@@ -155,10 +383,28 @@ fn process_html_file(path: &Path, relationships: &CodeRelationships) -> Result<(
         return Ok(());
     }
 
-    // Add custom footer and inject graphs
+    // Add custom footer, then inject only the single graph this page's filename maps to.
     let mut modified = add_custom_footer(&content);
-    modified = inject_call_graphs(&modified, relationships);
-    modified = inject_inheritance_graphs(&modified, relationships, path);
+    let file_name = path.file_name().and_then(|f| f.to_str()).unwrap_or("");
+    modified = match page_index.get(file_name) {
+        Some(DocPageItem::Function(func_name)) => {
+            let modified = inject_call_graph(&modified, func_name, relationships, path);
+            let modified = inject_cfg_graph(&modified, func_name, relationships, path);
+            inject_verus_contract_section(&modified, &[func_name.as_str()], relationships)
+        }
+        Some(DocPageItem::Type(type_name)) => {
+            let modified = inject_inheritance_graph(&modified, type_name, relationships, path);
+            let prefix = format!("{}::", type_name);
+            let method_names: Vec<&str> = relationships
+                .verus_contracts
+                .keys()
+                .filter(|name| name.starts_with(&prefix))
+                .map(String::as_str)
+                .collect();
+            inject_verus_contract_section(&modified, &method_names, relationships)
+        }
+        None => modified,
+    };
 
     // Write back if modified
     if modified != content {
@@ -187,22 +433,28 @@ fn add_custom_footer(html: &str) -> String {
     }
 }
 
-fn inject_call_graphs(html: &str, relationships: &CodeRelationships) -> String {
+fn inject_call_graph(
+    html: &str,
+    func_name: &str,
+    relationships: &CodeRelationships,
+    path: &Path,
+) -> String {
     let mut result = html.to_string();
 
-    // Look for function documentation sections
-    for (func_name, _metadata) in &relationships.functions {
-        // Try to generate a call graph for this function
-        if let Some(svg) = generate_function_call_graph(func_name, relationships) {
-            // Encode SVG as base64 for embedding
-            let svg_base64 = general_purpose::STANDARD.encode(&svg);
-            let data_uri = format!("data:image/svg+xml;base64,{}", svg_base64);
-
-            // Create a call graph section
-            let call_graph_html = format!(
-                "<h2 id=\"call-graph\"><a class=\"doc-anchor\" href=\"#call-graph\">§</a>Call Graph</h2>\n\
+    // Docs render the default (no cfg flags active) configuration, so callers/callees
+    // whose `#[cfg(...)]` doesn't hold with no flags set are filtered out rather than
+    // merged in.
+    let Some(svg) = generate_function_call_graph(func_name, relationships, path, &std::collections::HashSet::new())
+    else {
+        return result;
+    };
+
+    // Create a call graph section, with the SVG inlined so its nodes can carry
+    // real <a href> links into the rest of the docs instead of being dead pixels.
+    let call_graph_html = format!(
+        "<h2 id=\"call-graph\"><a class=\"doc-anchor\" href=\"#call-graph\">§</a>Call Graph</h2>\n\
 <div class=\"docblock\">\n    \
-    <img src=\"{}\" alt=\"Call graph for {}\" style=\"max-width: 100%; height: auto; margin: 10px 0;\" />\n    \
+    {}\n    \
     <p style=\"font-size: 0.9em; color: rgb(102, 102, 102);\">\n        \
         <strong>Legend:</strong>\n        \
         <span style=\"color: rgb(245, 124, 0);\">■</span> Callers →\n        \
@@ -210,62 +462,93 @@ fn inject_call_graphs(html: &str, relationships: &CodeRelationships) -> String {
         <span style=\"color: rgb(46, 125, 50);\">■</span> Callees\n    \
     </p>\n\
 </div>\n",
-                data_uri, func_name
-            );
+        svg
+    );
 
-            // Extract simple function name for matching
-            let simple_name = func_name.split("::").last().unwrap_or(func_name);
-
-            // Check if this HTML page is for this specific function
-            if result.contains(&format!(
-                "Function <span class=\"fn\">{}</span>",
-                simple_name
-            )) {
-                // Insert before </div></details> (the closing of the docblock)
-                if let Some(pos) = result.find("</div></details></section>") {
-                    result.insert_str(pos, &call_graph_html);
-                }
-            }
+    // Extract simple function name for matching
+    let simple_name = func_name.split("::").last().unwrap_or(func_name);
+
+    // Check if this HTML page is for this specific function
+    if result.contains(&format!(
+        "Function <span class=\"fn\">{}</span>",
+        simple_name
+    )) {
+        // Insert before </div></details> (the closing of the docblock)
+        if let Some(pos) = result.find("</div></details></section>") {
+            result.insert_str(pos, &call_graph_html);
         }
     }
 
     result
 }
 
-fn inject_inheritance_graphs(html: &str, relationships: &CodeRelationships, path: &Path) -> String {
+fn inject_cfg_graph(
+    html: &str,
+    func_name: &str,
+    relationships: &CodeRelationships,
+    path: &Path,
+) -> String {
     let mut result = html.to_string();
 
-    // Extract unique type names from inheritance info
-    let mut type_names: Vec<&String> = relationships
-        .inheritance
-        .values()
-        .map(|info| &info.type_name)
-        .collect();
-    type_names.sort();
-    type_names.dedup();
+    let Some(svg) = generate_function_cfg_graph(func_name, relationships, path) else {
+        return result;
+    };
 
-    // Look for struct/enum documentation sections
-    for type_name in type_names {
-        // Check if this file is for this type by looking at the filename
-        let file_name = path.file_name().and_then(|f| f.to_str()).unwrap_or("");
-        let simple_name = type_name.split("::").last().unwrap_or(type_name);
+    let cfg_graph_html = format!(
+        "<h2 id=\"control-flow-graph\"><a class=\"doc-anchor\" href=\"#control-flow-graph\">§</a>Control Flow Graph</h2>\n\
+<div class=\"docblock\">\n    \
+    {}\n    \
+    <p style=\"font-size: 0.9em; color: rgb(102, 102, 102);\">\n        \
+        <strong>Legend:</strong>\n        \
+        <span style=\"color: rgb(33, 150, 243);\">■</span> Entry\n        \
+        | <span style=\"color: rgb(244, 67, 54);\">■</span> Exit\n        \
+        | edge labels mark branch taken (<code>then</code>/<code>else</code>, match arms, <code>loop</code>/<code>exit</code>, <code>break</code>/<code>continue</code>/<code>return</code>)\n    \
+    </p>\n\
+</div>\n",
+        svg
+    );
+
+    let simple_name = func_name.split("::").last().unwrap_or(func_name);
+
+    if result.contains(&format!(
+        "Function <span class=\"fn\">{}</span>",
+        simple_name
+    )) {
+        if let Some(pos) = result.find("</div></details></section>") {
+            result.insert_str(pos, &cfg_graph_html);
+        }
+    }
+
+    result
+}
 
-        // Match struct.TypeName.html or enum.TypeName.html
-        let matches_file = file_name == format!("struct.{}.html", simple_name)
-            || file_name == format!("enum.{}.html", simple_name);
+fn inject_inheritance_graph(
+    html: &str,
+    type_name: &str,
+    relationships: &CodeRelationships,
+    path: &Path,
+) -> String {
+    let mut result = html.to_string();
 
-        if matches_file {
-            // Try to generate an inheritance graph for this type
-            if let Some(svg) = generate_type_inheritance_graph(type_name, relationships) {
-                // Encode SVG as base64 for embedding
-                let svg_base64 = general_purpose::STANDARD.encode(&svg);
-                let data_uri = format!("data:image/svg+xml;base64,{}", svg_base64);
+    // Docs render the default (no cfg flags active) configuration, so impls whose
+    // `#[cfg(...)]` doesn't hold with no flags set are filtered out rather than merged in.
+    let Some(svg) = generate_type_inheritance_graph(
+        type_name,
+        relationships,
+        path,
+        &std::collections::HashSet::new(),
+        GraphOptions::default(),
+    ) else {
+        return result;
+    };
 
-                // Create an inheritance graph section
-                let inheritance_graph_html = format!(
-                    "<h2 id=\"trait-graph\"><a class=\"doc-anchor\" href=\"#trait-graph\">§</a>Trait Implementation Graph</h2>\n\
+    // Create an inheritance graph section, with the SVG inlined so its
+    // nodes can carry real <a href> links into the rest of the docs
+    // instead of being dead pixels.
+    let inheritance_graph_html = format!(
+        "<h2 id=\"trait-graph\"><a class=\"doc-anchor\" href=\"#trait-graph\">§</a>Trait Implementation Graph</h2>\n\
 <div class=\"docblock\">\n    \
-    <img src=\"{}\" alt=\"Trait implementations for {}\" style=\"max-width: 100%; height: auto; margin: 10px 0;\" />\n    \
+    {}\n    \
     <p style=\"font-size: 0.9em; color: rgb(102, 102, 102);\">\n        \
         <strong>Legend:</strong>\n        \
         <span style=\"color: rgb(106, 27, 154);\">■</span> Traits\n        \
@@ -274,46 +557,324 @@ fn inject_inheritance_graphs(html: &str, relationships: &CodeRelationships, path
         | <span style=\"color: rgb(106, 27, 154);\">→</span> Implementation\n    \
     </p>\n\
 </div>\n",
-                    data_uri, type_name
-                );
-
-                // Insert after struct/enum description, before trait implementations section
-                // Try multiple patterns because the structure varies:
-                // - Structs without methods: </div></details><h2 id="trait-implementations"
-                // - Structs with methods: </div></details></div></details></div><h2 id="trait-implementations"
-
-                let inserted = if let Some(pos) = result
-                    .find("</div></details></div></details></div><h2 id=\"trait-implementations\"")
-                {
-                    // Struct with methods (implementations section)
-                    result.insert_str(
-                        pos + "</div></details></div></details></div>".len(),
-                        &inheritance_graph_html,
-                    );
-                    true
-                } else if let Some(pos) =
-                    result.find("</div></details><h2 id=\"trait-implementations\"")
-                {
-                    // Simple struct without methods
-                    result.insert_str(pos + "</div></details>".len(), &inheritance_graph_html);
-                    true
-                } else {
-                    false
-                };
-
-                if !inserted {
-                    eprintln!(
-                        "  Warning: Could not find insertion point for {} trait graph",
-                        simple_name
-                    );
-                }
-            }
-        }
+        svg
+    );
+
+    // Insert after struct/enum description, before trait implementations section
+    // Try multiple patterns because the structure varies:
+    // - Structs without methods: </div></details><h2 id="trait-implementations"
+    // - Structs with methods: </div></details></div></details></div><h2 id="trait-implementations"
+
+    let inserted = if let Some(pos) =
+        result.find("</div></details></div></details></div><h2 id=\"trait-implementations\"")
+    {
+        // Struct with methods (implementations section)
+        result.insert_str(
+            pos + "</div></details></div></details></div>".len(),
+            &inheritance_graph_html,
+        );
+        true
+    } else if let Some(pos) = result.find("</div></details><h2 id=\"trait-implementations\"") {
+        // Simple struct without methods
+        result.insert_str(pos + "</div></details>".len(), &inheritance_graph_html);
+        true
+    } else {
+        false
+    };
+
+    if !inserted {
+        let simple_name = type_name.split("::").last().unwrap_or(type_name);
+        eprintln!(
+            "  Warning: Could not find insertion point for {} trait graph",
+            simple_name
+        );
+    }
+
+    result
+}
+
+/// Render a "Verified Contract" section listing the `requires`/`ensures`/`invariant`/
+/// `decreases` clauses captured for each name in `names` that has a non-empty
+/// `VerusContract`. `names` is either a single function name or, for a type's page,
+/// every one of its `Type::method` entries — a struct with no verified methods simply
+/// gets nothing injected.
+fn inject_verus_contract_section(
+    html: &str,
+    names: &[&str],
+    relationships: &CodeRelationships,
+) -> String {
+    let entries: Vec<(&str, &VerusContract)> = names
+        .iter()
+        .filter_map(|name| {
+            relationships
+                .verus_contracts
+                .get(*name)
+                .map(|contract| (*name, contract))
+        })
+        .collect();
+
+    if entries.is_empty() {
+        return html.to_string();
+    }
+
+    let mut body = String::new();
+    for (name, contract) in entries {
+        let simple_name = name.split("::").last().unwrap_or(name);
+        body.push_str(&format!(
+            "    <h3>{}</h3>\n    <ul>\n",
+            simple_name
+        ));
+        body.push_str(&render_clause_list("requires", &contract.requires));
+        body.push_str(&render_clause_list("ensures", &contract.ensures));
+        body.push_str(&render_clause_list("invariant", &contract.invariant));
+        body.push_str(&render_clause_list("decreases", &contract.decreases));
+        body.push_str("    </ul>\n");
+    }
+
+    let contract_html = format!(
+        "<h2 id=\"verified-contract\"><a class=\"doc-anchor\" href=\"#verified-contract\">§</a>Verified Contract</h2>\n\
+<div class=\"docblock\">\n{}\
+    <p style=\"font-size: 0.9em; color: rgb(102, 102, 102);\">\n        \
+        Extracted from this item's <code>verus!</code> block.\n    \
+    </p>\n\
+</div>\n",
+        body
+    );
+
+    let mut result = html.to_string();
+    if let Some(pos) = result.find("</div></details></section>") {
+        result.insert_str(pos, &contract_html);
     }
 
     result
 }
 
+fn render_clause_list(label: &str, clauses: &[String]) -> String {
+    if clauses.is_empty() {
+        return String::new();
+    }
+
+    let items: String = clauses
+        .iter()
+        .map(|clause| format!("            <li><code>{}</code></li>\n", clause))
+        .collect();
+
+    format!(
+        "        <li><strong>{}</strong>\n        <ul>\n{}        </ul></li>\n",
+        label, items
+    )
+}
+
+/// Marks the search-index file as already carrying relationship data, mirroring
+/// `is_already_processed`'s role for the HTML pages.
+const RELATIONSHIP_SEARCH_MARKER: &str = "/* xtask relationship search index */";
+
+/// A small, self-contained script that renders `window.xtaskRelationships` hits
+/// alongside rustdoc's own search results, so searching for a function surfaces its
+/// callers/callees (and a type's implemented traits) as clickable results.
+const RELATIONSHIP_SEARCH_SCRIPT: &str = r#"
+(function () {
+    function render(term) {
+        var container = document.getElementById("xtask-relationships-results");
+        if (!container) {
+            container = document.createElement("div");
+            container.id = "xtask-relationships-results";
+            var results = document.getElementById("results") || document.body;
+            results.appendChild(container);
+        }
+        container.innerHTML = "";
+
+        var data = window.xtaskRelationships;
+        if (!data || !term) {
+            return;
+        }
+
+        var fn = data.functions[term];
+        if (fn) {
+            var section = document.createElement("div");
+            section.className = "xtask-relationships";
+            section.innerHTML =
+                "<h3>Relationships for " + term + "</h3>" +
+                "<p>Callers: " + (fn.callers.join(", ") || "none") + "</p>" +
+                "<p>Callees: " + (fn.callees.join(", ") || "none") + "</p>";
+            container.appendChild(section);
+        }
+
+        var ty = data.types[term];
+        if (ty) {
+            var section = document.createElement("div");
+            section.className = "xtask-relationships";
+            section.innerHTML =
+                "<h3>Traits implemented by " + term + "</h3>" +
+                "<p>" + (ty.traits.join(", ") || "none") + "</p>";
+            container.appendChild(section);
+        }
+    }
+
+    document.addEventListener("DOMContentLoaded", function () {
+        var input = document.getElementById("search-input");
+        if (input) {
+            input.addEventListener("input", function () {
+                render(input.value.trim());
+            });
+        }
+    });
+})();
+"#;
+
+/// Build the auxiliary function → callers/callees and type → traits payload that
+/// gets appended to rustdoc's search index.
+fn build_relationship_search_payload(relationships: &CodeRelationships) -> serde_json::Value {
+    let mut functions = serde_json::Map::new();
+    for name in relationships.functions.keys() {
+        let callers: Vec<&str> = relationships
+            .usage_graph
+            .get(name)
+            .into_iter()
+            .flatten()
+            .map(|(caller, _)| caller.as_str())
+            .collect();
+        let callees: Vec<&str> = relationships
+            .call_graph
+            .get(name)
+            .into_iter()
+            .flatten()
+            .map(|(callee, _)| callee.as_str())
+            .collect();
+
+        if callers.is_empty() && callees.is_empty() {
+            continue;
+        }
+
+        functions.insert(
+            name.clone(),
+            serde_json::json!({ "callers": callers, "callees": callees }),
+        );
+    }
+
+    let mut types = serde_json::Map::new();
+    for info in relationships.inheritance.values() {
+        let Some(trait_name) = &info.trait_name else {
+            continue;
+        };
+
+        types
+            .entry(info.type_name.clone())
+            .or_insert_with(|| serde_json::json!({ "traits": Vec::<String>::new() }))
+            .get_mut("traits")
+            .and_then(|traits| traits.as_array_mut())
+            .expect("traits entry is always inserted as an array")
+            .push(serde_json::json!(trait_name));
+    }
+
+    serde_json::json!({ "functions": functions, "types": types })
+}
+
+/// Locate rustdoc's generated `search-index*.js` directly under `doc_dir`.
+fn find_search_index_file(doc_dir: &Path) -> Result<Option<PathBuf>> {
+    for entry in fs::read_dir(doc_dir)? {
+        let entry = entry?;
+        let name = entry.file_name();
+        let name = name.to_string_lossy();
+
+        if name.starts_with("search-index") && name.ends_with(".js") {
+            return Ok(Some(entry.path()));
+        }
+    }
+
+    Ok(None)
+}
+
+/// Append the extracted relationships, and the script that renders them, to
+/// rustdoc's search index so that searching the docs surfaces callers/callees.
+fn inject_relationship_search_index(
+    doc_dir: &Path,
+    relationships: &CodeRelationships,
+) -> Result<()> {
+    let Some(search_index_path) = find_search_index_file(doc_dir)? else {
+        eprintln!("  No search-index file found, skipping relationship search injection");
+        return Ok(());
+    };
+
+    let content = fs::read_to_string(&search_index_path)?;
+    if content.contains(RELATIONSHIP_SEARCH_MARKER) {
+        println!("  Relationship search index already injected, skipping");
+        return Ok(());
+    }
+
+    let payload = build_relationship_search_payload(relationships);
+    let snippet = format!(
+        "\n{marker}\nwindow.xtaskRelationships = {payload};\n{script}\n",
+        marker = RELATIONSHIP_SEARCH_MARKER,
+        payload = serde_json::to_string(&payload)?,
+        script = RELATIONSHIP_SEARCH_SCRIPT,
+    );
+
+    fs::write(&search_index_path, content + &snippet)?;
+    println!(
+        "  Injected relationship data into {}",
+        search_index_path.display()
+    );
+
+    Ok(())
+}
+
+/// Crawl the whole crate's `inheritance` map once and write a standalone, browsable
+/// index site under `doc_dir`: one SVG per type (rendered in parallel via rayon, the
+/// same way `generate_and_process_docs` runs `process_html_file` over rustdoc's HTML
+/// files in parallel, since each `generate_type_inheritance_graph` call is independent), a JSON
+/// search index (`relationships::generate_index`), and a master HTML page linking
+/// every type to its graph.
+fn write_relationship_index(doc_dir: &Path, relationships: &CodeRelationships) -> Result<()> {
+    let mut type_names: Vec<&str> =
+        relationships.inheritance.values().map(|info| info.type_name.as_str()).collect();
+    type_names.sort_unstable();
+    type_names.dedup();
+
+    let active_cfg = std::collections::HashSet::new();
+    let svgs: Vec<(&str, Option<String>)> = type_names
+        .par_iter()
+        .map(|&type_name| {
+            let svg = generate_type_inheritance_graph(
+                type_name,
+                relationships,
+                doc_dir,
+                &active_cfg,
+                GraphOptions::default(),
+            );
+            (type_name, svg)
+        })
+        .collect();
+
+    for (type_name, svg) in &svgs {
+        if let Some(svg) = svg {
+            fs::write(doc_dir.join(format!("graph.{}.svg", type_name)), svg)?;
+        }
+    }
+
+    let index_json = generate_index(relationships);
+    fs::write(doc_dir.join("relationship-index.json"), serde_json::to_string_pretty(&index_json)?)?;
+
+    let links: String = svgs
+        .iter()
+        .filter(|(_, svg)| svg.is_some())
+        .map(|(type_name, _)| format!("    <li><a href=\"graph.{name}.svg\">{name}</a></li>\n", name = type_name))
+        .collect();
+
+    let rendered_count = svgs.iter().filter(|(_, svg)| svg.is_some()).count();
+    let html = format!(
+        "<!DOCTYPE html>\n<html>\n<head><title>Crate Relationship Index</title></head>\n<body>\n  \
+         <h1>Types</h1>\n  <ul>\n{links}  </ul>\n</body>\n</html>\n",
+        links = links
+    );
+    let html_path = doc_dir.join("relationship-index.html");
+    fs::write(&html_path, html)?;
+
+    println!("  Wrote relationship index for {} types to {}", rendered_count, html_path.display());
+
+    Ok(())
+}
+
 fn find_workspace_root() -> Result<std::path::PathBuf> {
     let mut current = env::current_dir()?;
 
@@ -377,4 +938,240 @@ mod tests {
             1
         );
     }
+
+    fn relationships_with_one_function_and_one_type() -> CodeRelationships {
+        use relationships::{FunctionMetadata, InheritanceInfo};
+
+        let mut relationships = CodeRelationships::default();
+        relationships.functions.insert(
+            "greet".to_string(),
+            FunctionMetadata {
+                name: "greet".to_string(),
+                fully_qualified_name: "greet".to_string(),
+                is_method: false,
+                is_public: true,
+                parent_type: None,
+                parent_trait: None,
+                file_path: PathBuf::from("src/lib.rs"),
+                cfg: None,
+                is_async: false,
+            },
+        );
+        relationships.functions.insert(
+            "GreeterBot::name".to_string(),
+            FunctionMetadata {
+                name: "name".to_string(),
+                fully_qualified_name: "GreeterBot::name".to_string(),
+                is_method: true,
+                is_public: true,
+                parent_type: Some("GreeterBot".to_string()),
+                parent_trait: Some("Named".to_string()),
+                file_path: PathBuf::from("src/lib.rs"),
+                cfg: None,
+                is_async: false,
+            },
+        );
+        relationships.inheritance.insert(
+            "GreeterBot".to_string(),
+            InheritanceInfo {
+                trait_name: None,
+                type_name: "GreeterBot".to_string(),
+                methods: vec!["name".to_string()],
+                method_cfgs: HashMap::new(),
+                bounds: Vec::new(),
+                parent_traits: Vec::new(),
+                generics: Vec::new(),
+                is_blanket: false,
+                cfg: None,
+            },
+        );
+        relationships
+    }
+
+    #[test]
+    fn test_build_page_index_maps_function_page() {
+        let relationships = relationships_with_one_function_and_one_type();
+        let index = build_page_index(&relationships);
+
+        assert!(matches!(
+            index.get("fn.greet.html"),
+            Some(DocPageItem::Function(name)) if name == "greet"
+        ));
+    }
+
+    #[test]
+    fn test_build_page_index_maps_type_page() {
+        let relationships = relationships_with_one_function_and_one_type();
+        let index = build_page_index(&relationships);
+
+        assert!(matches!(
+            index.get("struct.GreeterBot.html"),
+            Some(DocPageItem::Type(name)) if name == "GreeterBot"
+        ));
+        assert!(index.get("enum.GreeterBot.html").is_some());
+    }
+
+    #[test]
+    fn test_build_page_index_skips_methods() {
+        let relationships = relationships_with_one_function_and_one_type();
+        let index = build_page_index(&relationships);
+
+        assert!(index.get("fn.name.html").is_none());
+    }
+
+    fn sample_page_html() -> String {
+        "<html><body><section><div>desc</div></details></section></body></html>".to_string()
+    }
+
+    #[test]
+    fn test_inject_verus_contract_section_for_function() {
+        let mut relationships = CodeRelationships::default();
+        relationships.verus_contracts.insert(
+            "sum_two".to_string(),
+            VerusContract {
+                requires: vec!["a <= 1000000".to_string()],
+                ensures: vec!["result == a + b".to_string()],
+                invariant: Vec::new(),
+                decreases: Vec::new(),
+            },
+        );
+
+        let result =
+            inject_verus_contract_section(&sample_page_html(), &["sum_two"], &relationships);
+
+        assert!(result.contains("Verified Contract"));
+        assert!(result.contains("a &lt;= 1000000") || result.contains("a <= 1000000"));
+        assert!(result.contains("requires"));
+        assert!(result.contains("ensures"));
+    }
+
+    #[test]
+    fn test_inject_verus_contract_section_for_type_gathers_methods() {
+        let mut relationships = CodeRelationships::default();
+        relationships.verus_contracts.insert(
+            "BoundedVec::push".to_string(),
+            VerusContract {
+                requires: vec!["old(self).inv()".to_string()],
+                ensures: Vec::new(),
+                invariant: Vec::new(),
+                decreases: Vec::new(),
+            },
+        );
+
+        let result = inject_verus_contract_section(
+            &sample_page_html(),
+            &["BoundedVec::push"],
+            &relationships,
+        );
+
+        assert!(result.contains("Verified Contract"));
+        assert!(result.contains("push"));
+    }
+
+    #[test]
+    fn test_inject_verus_contract_section_skips_unverified_items() {
+        let relationships = CodeRelationships::default();
+
+        let result =
+            inject_verus_contract_section(&sample_page_html(), &["plain_fn"], &relationships);
+
+        assert_eq!(result, sample_page_html());
+    }
+
+    fn relationships_with_caller_and_callee() -> CodeRelationships {
+        let mut relationships = CodeRelationships::default();
+        relationships
+            .call_graph
+            .entry("foo".to_string())
+            .or_default()
+            .insert(("bar".to_string(), false));
+        relationships
+            .usage_graph
+            .entry("bar".to_string())
+            .or_default()
+            .insert(("foo".to_string(), false));
+        relationships.functions.insert(
+            "foo".to_string(),
+            relationships::FunctionMetadata {
+                name: "foo".to_string(),
+                fully_qualified_name: "foo".to_string(),
+                is_method: false,
+                is_public: true,
+                parent_type: None,
+                parent_trait: None,
+                file_path: PathBuf::from("src/lib.rs"),
+                cfg: None,
+                is_async: false,
+            },
+        );
+        relationships
+    }
+
+    #[test]
+    fn test_build_relationship_search_payload_includes_callees() {
+        let relationships = relationships_with_caller_and_callee();
+        let payload = build_relationship_search_payload(&relationships);
+
+        let callees = payload["functions"]["foo"]["callees"].as_array().unwrap();
+        assert!(callees.contains(&serde_json::json!("bar")));
+    }
+
+    #[test]
+    fn test_build_relationship_search_payload_omits_isolated_functions() {
+        let mut relationships = CodeRelationships::default();
+        relationships.functions.insert(
+            "lonely".to_string(),
+            relationships::FunctionMetadata {
+                name: "lonely".to_string(),
+                fully_qualified_name: "lonely".to_string(),
+                is_method: false,
+                is_public: true,
+                parent_type: None,
+                parent_trait: None,
+                file_path: PathBuf::from("src/lib.rs"),
+                cfg: None,
+                is_async: false,
+            },
+        );
+
+        let payload = build_relationship_search_payload(&relationships);
+
+        assert!(payload["functions"].get("lonely").is_none());
+    }
+
+    #[test]
+    fn test_find_search_index_file() {
+        let dir = std::env::temp_dir().join(format!(
+            "xtask-search-index-test-{:?}",
+            std::thread::current().id()
+        ));
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("search-index1.78.0.js"), "var searchIndex = {};").unwrap();
+
+        let found = find_search_index_file(&dir).unwrap();
+        assert_eq!(found, Some(dir.join("search-index1.78.0.js")));
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_write_relationship_index_writes_svg_json_and_html() {
+        let relationships = relationships_with_one_function_and_one_type();
+
+        let dir = std::env::temp_dir().join(format!(
+            "xtask-relationship-index-test-{:?}",
+            std::thread::current().id()
+        ));
+        fs::create_dir_all(&dir).unwrap();
+
+        write_relationship_index(&dir, &relationships).unwrap();
+
+        assert!(dir.join("graph.GreeterBot.svg").exists());
+        assert!(dir.join("relationship-index.json").exists());
+
+        let html = fs::read_to_string(dir.join("relationship-index.html")).unwrap();
+        assert!(html.contains("graph.GreeterBot.svg"));
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
 }
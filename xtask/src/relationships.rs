@@ -1,37 +1,91 @@
+use crate::svg_doc::Document;
+use proc_macro2::{Delimiter, TokenStream, TokenTree};
+use serde::{Deserialize, Serialize};
 use std::collections::{HashMap, HashSet};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use syn::parse::Parser;
+use syn::punctuated::Punctuated;
 use syn::visit::Visit;
-use syn::{Expr, ExprCall, ExprMethodCall, ImplItem, ItemFn, ItemImpl, ItemTrait};
+use syn::{
+    Block, Expr, ExprAssign, ExprAwait, ExprCall, ExprClosure, ExprMethodCall, ExprStruct, ImplItem,
+    ItemFn, ItemImpl, ItemMod, ItemTrait, ItemUse, Local, Macro, Token,
+};
 
 /// Complete code relationship data extracted from source files
-#[derive(Debug, Default)]
+#[derive(Debug, Default, Serialize, Deserialize)]
 pub struct CodeRelationships {
-    /// Function → Set of functions it calls (forward dependencies)
-    pub call_graph: HashMap<String, HashSet<String>>,
+    /// Function → set of (callee, awaited) edges (forward dependencies). `awaited`
+    /// is true when the call site was the receiver of an `.await` — e.g.
+    /// `foo().await` records `("foo", true)`, a plain `foo()` records `("foo", false)`.
+    pub call_graph: HashMap<String, HashSet<(String, bool)>>,
 
-    /// Function → Set of functions that call it (reverse dependencies)
-    pub usage_graph: HashMap<String, HashSet<String>>,
+    /// Function → set of (caller, awaited) edges (reverse dependencies), the mirror
+    /// image of `call_graph`: `awaited` reflects whether *that caller's* call site
+    /// awaited this function.
+    pub usage_graph: HashMap<String, HashSet<(String, bool)>>,
 
     /// Type/Trait → Implementation details
     pub inheritance: HashMap<String, InheritanceInfo>,
 
+    /// Trait → its own directly-declared supertraits (the `: Super1 + Super2` bound
+    /// on the `trait` item itself), for every trait definition seen in the crate —
+    /// independent of whether anything actually implements it. Doc-link resolution
+    /// uses it to recognise trait names. Graph rendering does not read it: supertrait
+    /// edges come from `InheritanceInfo::parent_traits` of the traits the type itself
+    /// implements, so a chain (`Derived: Base`, `Base: Root`, ...) is drawn only one
+    /// level up from each implemented trait.
+    pub trait_supertraits: HashMap<String, Vec<String>>,
+
     /// Function → Complete metadata
     pub functions: HashMap<String, FunctionMetadata>,
+
+    /// Function/method → Verus `requires`/`ensures`/`invariant`/`decreases` clauses,
+    /// for items defined inside a `verus! { ... }` block. Keyed the same way as
+    /// `functions` (`"name"` for free functions, `"Type::name"` for methods).
+    ///
+    /// Not part of the stable JSON export schema (see `export_relationships_json`);
+    /// skipped rather than serialized so the on-disk snapshot format doesn't churn
+    /// every time the Verus/CFG extractors grow new fields.
+    #[serde(skip)]
+    pub verus_contracts: HashMap<String, crate::verus_contracts::VerusContract>,
+
+    /// Function/method → intra-procedural control-flow graph, keyed the same way as
+    /// `functions`. Not part of the stable JSON export schema; see `verus_contracts`.
+    #[serde(skip)]
+    pub cfgs: HashMap<String, ControlFlowGraph>,
 }
 
 /// Information about trait implementations and inheritance
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[allow(dead_code)]
 pub struct InheritanceInfo {
     pub trait_name: Option<String>,
     pub type_name: String,
     pub methods: Vec<String>,
+    /// Each method's own effective `#[cfg(...)]` predicate (combined with the
+    /// enclosing `impl`'s, the same way [`FunctionMetadata::cfg`] is), keyed by
+    /// method name. `None` for a method that inherits no cfg gating at all.
+    pub method_cfgs: HashMap<String, Option<String>>,
+    /// Per-parameter bound text collected from the `impl`'s generic parameter list
+    /// and `where` clause (e.g. `"T: Clone + Debug"`), one entry per bounded
+    /// parameter. Empty for a non-generic impl.
     pub bounds: Vec<String>,
     pub parent_traits: Vec<String>,
+    /// The `impl`'s own generic type parameters (e.g. `["T"]` for
+    /// `impl<T: Clone> Trait for Vec<T>`), empty for a concrete impl.
+    pub generics: Vec<String>,
+    /// Whether `self_ty` is a bare generic parameter (`impl<T> Trait for T`) —
+    /// a blanket impl, as opposed to a concrete type or a generic container like
+    /// `Vec<T>`/`Option<T>`.
+    pub is_blanket: bool,
+    /// The `#[cfg(...)]` predicate gating the `impl` block this info was extracted
+    /// from, as the raw token text inside the attribute's parens (e.g. `"test"`,
+    /// `"not (test)"`). `None` when the impl carries no `cfg` attribute at all.
+    pub cfg: Option<String>,
 }
 
 /// Complete metadata about a function
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct FunctionMetadata {
     pub name: String,
     pub fully_qualified_name: String,
@@ -42,6 +96,91 @@ pub struct FunctionMetadata {
     #[allow(dead_code)]
     pub parent_trait: Option<String>,
     pub file_path: PathBuf,
+    /// The function's effective `#[cfg(...)]` predicate: its own attribute combined
+    /// with its enclosing `impl` block's (as `all(impl_cfg, fn_cfg)` when both are
+    /// present), so a method under `#[cfg(test)] impl Foo { #[cfg(unix)] fn bar() }`
+    /// carries both conditions. `None` when nothing in that chain is cfg-gated.
+    pub cfg: Option<String>,
+    /// Whether this function/method is declared `async`. The call-graph renderer
+    /// does not read this field: it styles each edge by that edge's own `awaited`
+    /// flag alone.
+    pub is_async: bool,
+}
+
+/// Serialize `relationships` to the stable, pretty-printed JSON schema
+/// (`{ "functions", "call_graph", "usage_graph", "inheritance", "trait_supertraits" }`,
+/// plus the `#[serde(skip)]`-exempt CFG/Verus fields omitted) so other tools can diff
+/// relationship snapshots between revisions without re-parsing the crate.
+pub fn export_relationships_json(relationships: &CodeRelationships) -> String {
+    serde_json::to_string_pretty(relationships)
+        .expect("CodeRelationships fields are all JSON-safe (Strings, collections, PathBuf)")
+}
+
+/// Load a `CodeRelationships` snapshot previously written by
+/// `export_relationships_json`. The CFG and Verus-contract maps come back empty,
+/// since they're intentionally excluded from the exported schema.
+pub fn import_relationships_json(json: &str) -> serde_json::Result<CodeRelationships> {
+    serde_json::from_str(json)
+}
+
+/// Crawl the whole crate's `inheritance` map once and build a flat, crate-wide
+/// search index — every type, trait, and method, each with the type that owns it
+/// — as a JSON value ready to drop into a standalone index page (unlike
+/// `build_relationship_search_payload` in `main.rs`, which instead augments
+/// rustdoc's own per-page search index). Types and traits get one entry per name, so
+/// a trait implemented by several types still appears once; methods get one entry per
+/// `(name, owning type)` pair.
+pub fn generate_index(relationships: &CodeRelationships) -> serde_json::Value {
+    let mut types = std::collections::BTreeSet::new();
+    let mut traits = std::collections::BTreeSet::new();
+    let mut methods = std::collections::BTreeMap::new();
+
+    for info in relationships.inheritance.values() {
+        types.insert(info.type_name.clone());
+        if let Some(trait_name) = &info.trait_name {
+            traits.insert(trait_name.clone());
+        }
+        for method in &info.methods {
+            methods
+                .entry((method.clone(), info.type_name.clone()))
+                .or_insert(());
+        }
+    }
+
+    serde_json::json!({
+        "types": types.into_iter().map(|name| serde_json::json!({ "name": name })).collect::<Vec<_>>(),
+        "traits": traits.into_iter().map(|name| serde_json::json!({ "name": name })).collect::<Vec<_>>(),
+        "methods": methods.into_keys()
+            .map(|(name, owner)| serde_json::json!({ "name": name, "owner": owner }))
+            .collect::<Vec<_>>(),
+    })
+}
+
+/// A single basic block in a function's control-flow graph: a straight-line run of
+/// statements with no internal branches.
+#[derive(Debug, Clone, Default)]
+pub struct CfgBlock {
+    pub id: usize,
+    pub statements: Vec<String>,
+    pub successors: Vec<CfgEdge>,
+}
+
+/// An edge out of a `CfgBlock`, optionally labeled (e.g. `"then"`/`"else"`, a match
+/// arm pattern, `"break"`/`"continue"`).
+#[derive(Debug, Clone)]
+pub struct CfgEdge {
+    pub target: usize,
+    pub label: Option<String>,
+}
+
+/// A function's intra-procedural control-flow graph: basic blocks connected by
+/// (possibly labeled) edges, with a dedicated entry and exit block — mirrors the
+/// shape of rustc's own MIR `cfg::construct` output, minus the rest of MIR.
+#[derive(Debug, Clone)]
+pub struct ControlFlowGraph {
+    pub blocks: Vec<CfgBlock>,
+    pub entry: usize,
+    pub exit: usize,
 }
 
 /// Context for tracking scope during AST traversal
@@ -51,7 +190,18 @@ struct Context {
     current_type: Option<String>,
     current_trait: Option<String>,
     current_impl_trait: Option<String>,
+    /// The `#[cfg(...)]` predicate of the `impl` block currently being visited, if
+    /// any — combined with a method's own `cfg` attribute to produce its effective
+    /// [`FunctionMetadata::cfg`].
+    current_impl_cfg: Option<String>,
     module_path: Vec<String>,
+    /// Local variable name → statically known type, as inferred from type
+    /// ascriptions and `Type::new(...)`/struct-literal initializers in the
+    /// function currently being visited. A stack of scopes, innermost last, so a
+    /// `{ .. }` block or closure body can shadow an outer binding without
+    /// clobbering it — the scope is popped again once that block/closure is done.
+    /// Cleared (reset to empty) on entry to each function body.
+    locals: Vec<HashMap<String, String>>,
 }
 
 impl Context {
@@ -65,22 +215,78 @@ impl Context {
         parts.push(name.to_string());
         parts.join("::")
     }
+
+    fn push_scope(&mut self) {
+        self.locals.push(HashMap::new());
+    }
+
+    fn pop_scope(&mut self) {
+        self.locals.pop();
+    }
+
+    /// Bind `name` in the innermost scope — the right behavior for a fresh `let`,
+    /// which always introduces a new binding (shadowing any outer one of the same
+    /// name) rather than mutating it.
+    fn bind_local(&mut self, name: String, ty: String) {
+        if let Some(scope) = self.locals.last_mut() {
+            scope.insert(name, ty);
+        }
+    }
+
+    /// Look up `name` from the innermost scope outward, the way name resolution
+    /// actually works for nested blocks/closures.
+    fn lookup_local(&self, name: &str) -> Option<&String> {
+        self.locals.iter().rev().find_map(|scope| scope.get(name))
+    }
+
+    /// Record a plain reassignment (`x = ...;`) to an *existing* binding: updates
+    /// the scope that already holds `name` in place (so shadowing semantics are
+    /// preserved) to `ty`, or drops the binding entirely when `ty` is `None` — an
+    /// assignment whose RHS type can't be inferred makes the variable's type
+    /// unknown again rather than keeping a now-possibly-stale guess.
+    fn reassign_local(&mut self, name: &str, ty: Option<String>) {
+        for scope in self.locals.iter_mut().rev() {
+            if scope.contains_key(name) {
+                match ty {
+                    Some(ty) => {
+                        scope.insert(name.to_string(), ty);
+                    }
+                    None => {
+                        scope.remove(name);
+                    }
+                }
+                return;
+            }
+        }
+    }
 }
 
 /// AST visitor for extracting relationships
 struct RelationshipVisitor<'a> {
     context: Context,
-    call_graph: &'a mut HashMap<String, HashSet<String>>,
+    call_graph: &'a mut HashMap<String, HashSet<(String, bool)>>,
     inheritance: &'a mut HashMap<String, InheritanceInfo>,
     functions: &'a mut HashMap<String, FunctionMetadata>,
+    cfgs: &'a mut HashMap<String, ControlFlowGraph>,
+    /// Imported leaf name (or `as` alias) → fully-qualified path, accumulated from
+    /// every `use` item across all source files. Calls are resolved against this
+    /// after the whole crate has been visited, in `resolve_call_graph`.
+    imports: &'a mut HashMap<String, String>,
+    /// `use a::b::*` targets, recorded but not resolved against — which specific
+    /// name a glob brings into scope isn't known without full crate name
+    /// resolution, so these are kept only for inspection/debugging.
+    import_globs: &'a mut Vec<String>,
     file_path: PathBuf,
 }
 
 impl<'a> RelationshipVisitor<'a> {
     fn new(
-        call_graph: &'a mut HashMap<String, HashSet<String>>,
+        call_graph: &'a mut HashMap<String, HashSet<(String, bool)>>,
         inheritance: &'a mut HashMap<String, InheritanceInfo>,
         functions: &'a mut HashMap<String, FunctionMetadata>,
+        cfgs: &'a mut HashMap<String, ControlFlowGraph>,
+        imports: &'a mut HashMap<String, String>,
+        import_globs: &'a mut Vec<String>,
         file_path: PathBuf,
     ) -> Self {
         RelationshipVisitor {
@@ -88,15 +294,18 @@ impl<'a> RelationshipVisitor<'a> {
             call_graph,
             inheritance,
             functions,
+            cfgs,
+            imports,
+            import_globs,
             file_path,
         }
     }
 
-    fn add_call(&mut self, caller: &str, callee: &str) {
+    fn add_call(&mut self, caller: &str, callee: &str, awaited: bool) {
         self.call_graph
             .entry(caller.to_string())
             .or_insert_with(HashSet::new)
-            .insert(callee.to_string());
+            .insert((callee.to_string(), awaited));
     }
 
     fn extract_path_name(path: &syn::Path) -> String {
@@ -106,6 +315,329 @@ impl<'a> RelationshipVisitor<'a> {
             .collect::<Vec<_>>()
             .join("::")
     }
+
+    /// An `impl`'s own generic type parameter names, e.g. `["T"]` for
+    /// `impl<T: Clone> Trait for Vec<T>`.
+    fn generic_param_names(generics: &syn::Generics) -> Vec<String> {
+        generics.type_params().map(|p| p.ident.to_string()).collect()
+    }
+
+    /// A single `TypeParamBound` rendered as a trait name, the way it'd read in a
+    /// `where` clause (`Clone`, `Debug`, ...). Lifetime bounds (`'a`) carry no
+    /// trait name and are dropped.
+    fn bound_to_string(bound: &syn::TypeParamBound) -> Option<String> {
+        match bound {
+            syn::TypeParamBound::Trait(trait_bound) => Some(Self::extract_path_name(&trait_bound.path)),
+            _ => None,
+        }
+    }
+
+    /// Render an `impl`'s generic bounds as `"Param: Bound1 + Bound2"` strings, one
+    /// per bounded parameter — both the inline form (`impl<T: Clone>`) and the
+    /// `where`-clause form (`impl<T> ... where T: Clone`) contribute, so a caller
+    /// sees the effective bound regardless of which syntax the impl used.
+    fn generic_bounds(generics: &syn::Generics) -> Vec<String> {
+        let mut bounds = Vec::new();
+
+        for param in generics.type_params() {
+            let bound_str = param
+                .bounds
+                .iter()
+                .filter_map(Self::bound_to_string)
+                .collect::<Vec<_>>()
+                .join(" + ");
+            if !bound_str.is_empty() {
+                bounds.push(format!("{}: {}", param.ident, bound_str));
+            }
+        }
+
+        if let Some(where_clause) = &generics.where_clause {
+            for predicate in &where_clause.predicates {
+                if let syn::WherePredicate::Type(pred) = predicate {
+                    if let syn::Type::Path(type_path) = &pred.bounded_ty {
+                        let name = Self::extract_path_name(&type_path.path);
+                        let bound_str = pred
+                            .bounds
+                            .iter()
+                            .filter_map(Self::bound_to_string)
+                            .collect::<Vec<_>>()
+                            .join(" + ");
+                        if !bound_str.is_empty() {
+                            bounds.push(format!("{}: {}", name, bound_str));
+                        }
+                    }
+                }
+            }
+        }
+
+        bounds
+    }
+
+    /// Infer a `let` binding's type from its initializer: `Type::new(...)` and
+    /// other associated-function calls yield the type before the last `::`
+    /// segment, and struct-literal initializers (`Type { .. }`) yield the
+    /// literal's own path. Anything else (literals, method calls, etc.) is left
+    /// unresolved.
+    fn infer_init_type(expr: &Expr) -> Option<String> {
+        match expr {
+            Expr::Call(ExprCall { func, .. }) => {
+                if let Expr::Path(expr_path) = &**func {
+                    let path = Self::extract_path_name(&expr_path.path);
+                    path.rsplit_once("::").map(|(type_name, _method)| type_name.to_string())
+                } else {
+                    None
+                }
+            }
+            Expr::Struct(ExprStruct { path, .. }) => Some(Self::extract_path_name(path)),
+            _ => None,
+        }
+    }
+
+    /// Resolve a method-call receiver to a statically known type: `self`
+    /// resolves to the enclosing `impl`'s type, and a bare variable resolves to
+    /// whatever `visit_local` inferred for it. Anything else (a chained call,
+    /// a field access, a literal) is left unresolved and falls back to a bare
+    /// method-name edge.
+    fn resolve_receiver_type(&self, receiver: &Expr) -> Option<String> {
+        let Expr::Path(expr_path) = receiver else {
+            return None;
+        };
+        let name = Self::extract_path_name(&expr_path.path);
+
+        if name == "self" {
+            self.context.current_type.clone()
+        } else {
+            self.context.lookup_local(&name).cloned()
+        }
+    }
+
+    /// Combine an enclosing `impl`'s cfg predicate with an item's own, the way a
+    /// build actually evaluates nested `cfg` attributes: both must hold, so the
+    /// pair is folded into `all(outer, inner)` when both are present.
+    fn combine_cfg(outer: Option<&String>, inner: Option<&String>) -> Option<String> {
+        match (outer, inner) {
+            (Some(outer), Some(inner)) => Some(format!("all({}, {})", outer, inner)),
+            (Some(outer), None) => Some(outer.clone()),
+            (None, Some(inner)) => Some(inner.clone()),
+            (None, None) => None,
+        }
+    }
+
+    /// Look up whether `type_name` implements a trait that declares `method_name`,
+    /// so a resolved `Type::method` call can also be recorded against its
+    /// trait-qualified form (mirrors rust-analyzer falling back from the inherent
+    /// impl to trait impls in scope).
+    fn trait_declaring_method(&self, type_name: &str, method_name: &str) -> Option<String> {
+        self.inheritance.values().find_map(|info| {
+            if info.type_name == type_name && info.methods.iter().any(|m| m == method_name) {
+                info.trait_name.clone()
+            } else {
+                None
+            }
+        })
+    }
+
+    /// Record the call/method-call edge `node` represents (if it is one) against
+    /// `current_fn`, tagged with whether its call site was the receiver of an
+    /// `.await` — the method-call/call detection arms themselves are unchanged
+    /// from before `await` tracking; only the `awaited` flag threaded to
+    /// `add_call` is new.
+    fn record_call_expr(&mut self, current_fn: &str, node: &Expr, awaited: bool) {
+        match node {
+            // Method calls: obj.method() — resolved to `Type::method` when the
+            // receiver's type is known, with the trait-qualified form recorded
+            // alongside it when that type implements a trait declaring the method.
+            Expr::MethodCall(ExprMethodCall { receiver, method, .. }) => {
+                let method_name = method.to_string();
+                match self.resolve_receiver_type(receiver) {
+                    Some(type_name) => {
+                        self.add_call(current_fn, &format!("{}::{}", type_name, method_name), awaited);
+                        if let Some(trait_name) = self.trait_declaring_method(&type_name, &method_name)
+                        {
+                            self.add_call(
+                                current_fn,
+                                &format!("{}::{}", trait_name, method_name),
+                                awaited,
+                            );
+                        }
+                    }
+                    None => self.add_call(current_fn, &method_name, awaited),
+                }
+            }
+            // Direct function calls: foo(), Type::method(), and fully-qualified
+            // calls like <Type as Trait>::method()
+            Expr::Call(ExprCall { func, .. }) => {
+                if let Expr::Path(expr_path) = &**func {
+                    let callee = match &expr_path.qself {
+                        // `<Type as Trait>::method` resolves to the concrete
+                        // receiver, not the trait, so record it against `Type`.
+                        Some(qself) => {
+                            if let syn::Type::Path(self_type) = &*qself.ty {
+                                let self_name = Self::extract_path_name(&self_type.path);
+                                let method = expr_path
+                                    .path
+                                    .segments
+                                    .last()
+                                    .map(|s| s.ident.to_string())
+                                    .unwrap_or_default();
+                                format!("{}::{}", self_name, method)
+                            } else {
+                                Self::extract_path_name(&expr_path.path)
+                            }
+                        }
+                        None => Self::extract_path_name(&expr_path.path),
+                    };
+                    self.add_call(current_fn, &callee, awaited);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Mirrors `RelationshipVisitor::visit_expr`'s call-resolution arms, but over a
+/// macro's re-parsed token stream rather than the original source tree. Kept as
+/// its own type because that tree is a throwaway re-parse of a cloned
+/// `TokenStream` with no relation to the real source file's `'a` lifetime, so it
+/// can't be walked with the outer `impl Visit<'a> for RelationshipVisitor<'a>`.
+struct MacroCallCollector<'v, 'a> {
+    visitor: &'v mut RelationshipVisitor<'a>,
+    current_fn: String,
+}
+
+impl<'v, 'a, 'b> Visit<'b> for MacroCallCollector<'v, 'a> {
+    fn visit_expr(&mut self, node: &'b Expr) {
+        match node {
+            Expr::MethodCall(ExprMethodCall { receiver, method, .. }) => {
+                let method_name = method.to_string();
+                match self.visitor.resolve_receiver_type(receiver) {
+                    Some(type_name) => {
+                        self.visitor.add_call(
+                            &self.current_fn,
+                            &format!("{}::{}", type_name, method_name),
+                            false,
+                        );
+                        if let Some(trait_name) =
+                            self.visitor.trait_declaring_method(&type_name, &method_name)
+                        {
+                            self.visitor.add_call(
+                                &self.current_fn,
+                                &format!("{}::{}", trait_name, method_name),
+                                false,
+                            );
+                        }
+                    }
+                    None => self.visitor.add_call(&self.current_fn, &method_name, false),
+                }
+            }
+            Expr::Call(ExprCall { func, .. }) => {
+                if let Expr::Path(expr_path) = &**func {
+                    let callee = match &expr_path.qself {
+                        Some(qself) => {
+                            if let syn::Type::Path(self_type) = &*qself.ty {
+                                let self_name =
+                                    RelationshipVisitor::extract_path_name(&self_type.path);
+                                let method = expr_path
+                                    .path
+                                    .segments
+                                    .last()
+                                    .map(|s| s.ident.to_string())
+                                    .unwrap_or_default();
+                                format!("{}::{}", self_name, method)
+                            } else {
+                                RelationshipVisitor::extract_path_name(&expr_path.path)
+                            }
+                        }
+                        None => RelationshipVisitor::extract_path_name(&expr_path.path),
+                    };
+                    self.visitor.add_call(&self.current_fn, &callee, false);
+                }
+            }
+            _ => {}
+        }
+
+        syn::visit::visit_expr(self, node);
+    }
+
+    fn visit_macro(&mut self, node: &'b Macro) {
+        let current_fn = self.current_fn.clone();
+        collect_calls_from_macro(self.visitor, &current_fn, node);
+    }
+}
+
+/// Recover call/method-call expressions buried in a macro invocation's argument
+/// tokens: `syn` only sees a macro call as an opaque `TokenStream`, so without this
+/// the call graph misses everything inside `println!(...)`, `vec![...]`,
+/// `assert_eq!(...)`, and user macros alike. The macro's own name is always
+/// recorded as a call too, on the theory that expanding it is itself a call.
+///
+/// The token stream is tried, in order, as a comma-separated expression list (the
+/// shape almost every formatting/collection macro uses), then as a sequence of
+/// statements (for block-bodied macros like `my_macro! { do_thing(); }`), and
+/// finally falls back to a shallow scan for `name(`/`a::b(` shapes so a macro with
+/// genuinely non-Rust argument syntax still contributes a best-effort edge.
+fn collect_calls_from_macro(visitor: &mut RelationshipVisitor, current_fn: &str, mac: &Macro) {
+    let macro_name = RelationshipVisitor::extract_path_name(&mac.path);
+    visitor.add_call(current_fn, &macro_name, false);
+
+    let tokens = mac.tokens.clone();
+
+    if let Ok(exprs) =
+        Punctuated::<Expr, Token![,]>::parse_terminated.parse2(tokens.clone())
+    {
+        for expr in exprs.iter() {
+            MacroCallCollector { visitor, current_fn: current_fn.to_string() }.visit_expr(expr);
+        }
+        return;
+    }
+
+    if let Ok(stmts) = Block::parse_within.parse2(tokens.clone()) {
+        for stmt in &stmts {
+            MacroCallCollector { visitor, current_fn: current_fn.to_string() }.visit_stmt(stmt);
+        }
+        return;
+    }
+
+    scan_tokens_for_calls(visitor, current_fn, tokens);
+}
+
+/// Last-resort fallback when a macro's tokens don't parse as expressions or
+/// statements at all (a custom DSL, `quote!`-style syntax, and the like): walk the
+/// raw token tree looking for `ident(`/`a::b(` shapes and record each as a bare
+/// call, the same way an unresolved method-call receiver falls back to a bare
+/// method name elsewhere in this module. Recurses into nested delimited groups so
+/// calls inside `{ }`/`[ ]` blocks are still found.
+fn scan_tokens_for_calls(visitor: &mut RelationshipVisitor, current_fn: &str, tokens: TokenStream) {
+    let trees: Vec<TokenTree> = tokens.into_iter().collect();
+    let mut i = 0;
+
+    while i < trees.len() {
+        let mut path = Vec::new();
+        while let Some(TokenTree::Ident(ident)) = trees.get(i) {
+            path.push(ident.to_string());
+            i += 1;
+            let is_path_sep = matches!(trees.get(i), Some(TokenTree::Punct(p)) if p.as_char() == ':')
+                && matches!(trees.get(i + 1), Some(TokenTree::Punct(p)) if p.as_char() == ':');
+            if is_path_sep {
+                i += 2;
+            } else {
+                break;
+            }
+        }
+
+        if !path.is_empty() {
+            if matches!(trees.get(i), Some(TokenTree::Group(g)) if g.delimiter() == Delimiter::Parenthesis)
+            {
+                visitor.add_call(current_fn, &path.join("::"), false);
+            }
+            continue;
+        }
+
+        if let Some(TokenTree::Group(group)) = trees.get(i) {
+            scan_tokens_for_calls(visitor, current_fn, group.stream());
+        }
+        i += 1;
+    }
 }
 
 impl<'a> Visit<'a> for RelationshipVisitor<'a> {
@@ -124,11 +656,17 @@ impl<'a> Visit<'a> for RelationshipVisitor<'a> {
                 parent_type: self.context.current_type.clone(),
                 parent_trait: self.context.current_trait.clone(),
                 file_path: self.file_path.clone(),
+                cfg: extract_cfg_attr(&node.attrs),
+                is_async: node.sig.asyncness.is_some(),
             },
         );
 
+        self.cfgs
+            .insert(qualified_name.clone(), build_control_flow_graph(&node.block));
+
         // Set current function context
         let prev_function = self.context.current_function.clone();
+        let prev_locals = std::mem::take(&mut self.context.locals);
         self.context.current_function = Some(qualified_name.clone());
 
         // Visit function body
@@ -136,6 +674,7 @@ impl<'a> Visit<'a> for RelationshipVisitor<'a> {
 
         // Restore previous context
         self.context.current_function = prev_function;
+        self.context.locals = prev_locals;
     }
 
     fn visit_item_impl(&mut self, node: &'a ItemImpl) {
@@ -146,6 +685,20 @@ impl<'a> Visit<'a> for RelationshipVisitor<'a> {
             "Unknown".to_string()
         };
 
+        let generics = Self::generic_param_names(&node.generics);
+
+        // A blanket impl's self type is a bare generic parameter (`impl<T> Trait
+        // for T`) rather than a concrete type or a generic container like
+        // `Vec<T>`/`Option<T>` — those still name a real type (`Vec`, `Option`)
+        // once `extract_path_name` drops the angle-bracketed arguments.
+        let is_blanket = matches!(&*node.self_ty, syn::Type::Path(type_path)
+            if type_path.qself.is_none()
+                && type_path.path.segments.len() == 1
+                && type_path.path.segments[0].arguments.is_empty()
+                && generics.contains(&type_path.path.segments[0].ident.to_string()));
+
+        let bounds = Self::generic_bounds(&node.generics);
+
         // Extract trait name if this is a trait impl
         let trait_name = node
             .trait_
@@ -155,14 +708,22 @@ impl<'a> Visit<'a> for RelationshipVisitor<'a> {
         // Set context
         let prev_type = self.context.current_type.clone();
         let prev_impl_trait = self.context.current_impl_trait.clone();
+        let prev_impl_cfg = self.context.current_impl_cfg.clone();
+        let impl_cfg = extract_cfg_attr(&node.attrs);
         self.context.current_type = Some(type_name.clone());
         self.context.current_impl_trait = trait_name.clone();
+        self.context.current_impl_cfg = impl_cfg.clone();
 
-        // Collect methods
+        // Collect methods, along with each one's cfg predicate combined with the
+        // enclosing impl's (mirroring how FunctionMetadata::cfg is derived below).
         let mut methods = Vec::new();
+        let mut method_cfgs = HashMap::new();
         for item in &node.items {
             if let ImplItem::Fn(method) = item {
-                methods.push(method.sig.ident.to_string());
+                let method_name = method.sig.ident.to_string();
+                let combined_cfg = Self::combine_cfg(impl_cfg.as_ref(), extract_cfg_attr(&method.attrs).as_ref());
+                method_cfgs.insert(method_name.clone(), combined_cfg);
+                methods.push(method_name);
             }
         }
 
@@ -179,8 +740,12 @@ impl<'a> Visit<'a> for RelationshipVisitor<'a> {
                 trait_name,
                 type_name,
                 methods,
-                bounds: Vec::new(),
+                method_cfgs,
+                bounds,
                 parent_traits: Vec::new(),
+                generics,
+                is_blanket,
+                cfg: impl_cfg,
             },
         );
 
@@ -190,6 +755,7 @@ impl<'a> Visit<'a> for RelationshipVisitor<'a> {
         // Restore context
         self.context.current_type = prev_type;
         self.context.current_impl_trait = prev_impl_trait;
+        self.context.current_impl_cfg = prev_impl_cfg;
     }
 
     fn visit_item_trait(&mut self, node: &'a ItemTrait) {
@@ -214,8 +780,12 @@ impl<'a> Visit<'a> for RelationshipVisitor<'a> {
                     trait_name: Some(trait_name.clone()),
                     type_name: "__trait_definition__".to_string(),
                     methods: Vec::new(),
+                    method_cfgs: HashMap::new(),
                     bounds: Vec::new(),
                     parent_traits: supertraits,
+                    generics: Vec::new(),
+                    is_blanket: false,
+                    cfg: extract_cfg_attr(&node.attrs),
                 },
             );
         }
@@ -224,6 +794,20 @@ impl<'a> Visit<'a> for RelationshipVisitor<'a> {
         syn::visit::visit_item_trait(self, node);
     }
 
+    fn visit_item_mod(&mut self, node: &'a ItemMod) {
+        self.context.module_path.push(node.ident.to_string());
+
+        // Continue visiting nested items (only present for an inline `mod foo { ... }`)
+        syn::visit::visit_item_mod(self, node);
+
+        self.context.module_path.pop();
+    }
+
+    fn visit_item_use(&mut self, node: &'a ItemUse) {
+        collect_use_tree(&node.tree, Vec::new(), self.imports, self.import_globs);
+        syn::visit::visit_item_use(self, node);
+    }
+
     fn visit_impl_item_fn(&mut self, node: &'a syn::ImplItemFn) {
         let fn_name = node.sig.ident.to_string();
         let qualified_name = self.context.qualified_name(&fn_name);
@@ -239,11 +823,20 @@ impl<'a> Visit<'a> for RelationshipVisitor<'a> {
                 parent_type: self.context.current_type.clone(),
                 parent_trait: self.context.current_impl_trait.clone(),
                 file_path: self.file_path.clone(),
+                cfg: Self::combine_cfg(
+                    self.context.current_impl_cfg.as_ref(),
+                    extract_cfg_attr(&node.attrs).as_ref(),
+                ),
+                is_async: node.sig.asyncness.is_some(),
             },
         );
 
+        self.cfgs
+            .insert(qualified_name.clone(), build_control_flow_graph(&node.block));
+
         // Set current function context
         let prev_function = self.context.current_function.clone();
+        let prev_locals = std::mem::take(&mut self.context.locals);
         self.context.current_function = Some(qualified_name.clone());
 
         // Visit method body
@@ -251,30 +844,445 @@ impl<'a> Visit<'a> for RelationshipVisitor<'a> {
 
         // Restore previous context
         self.context.current_function = prev_function;
+        self.context.locals = prev_locals;
+    }
+
+    fn visit_local(&mut self, node: &'a Local) {
+        if let syn::Pat::Type(pat_type) = &node.pat {
+            if let (syn::Pat::Ident(pat_ident), syn::Type::Path(type_path)) =
+                (&*pat_type.pat, &*pat_type.ty)
+            {
+                self.context
+                    .bind_local(pat_ident.ident.to_string(), Self::extract_path_name(&type_path.path));
+            }
+        } else if let syn::Pat::Ident(pat_ident) = &node.pat {
+            if let Some(init) = &node.init {
+                if let Some(type_name) = Self::infer_init_type(&init.expr) {
+                    self.context.bind_local(pat_ident.ident.to_string(), type_name);
+                }
+            }
+        }
+
+        syn::visit::visit_local(self, node);
     }
 
     fn visit_expr(&mut self, node: &'a Expr) {
-        if let Some(current_fn) = self.context.current_function.clone() {
-            match node {
-                // Method calls: obj.method()
-                Expr::MethodCall(ExprMethodCall { method, .. }) => {
-                    let callee = method.to_string();
-                    self.add_call(&current_fn, &callee);
+        // `foo().await`/`obj.method().await`: record the call one level down
+        // (`node.base`) as awaited, then manually visit its own children
+        // (receiver/args/func) instead of falling through to the generic
+        // traversal below — that would revisit `base` itself and re-record it a
+        // second time as an ordinary, non-awaited call.
+        if let Expr::Await(ExprAwait { base, .. }) = node {
+            if let Some(current_fn) = self.context.current_function.clone() {
+                self.record_call_expr(&current_fn, base, true);
+            }
+            match &**base {
+                Expr::MethodCall(ExprMethodCall { receiver, args, .. }) => {
+                    self.visit_expr(receiver);
+                    for arg in args {
+                        self.visit_expr(arg);
+                    }
                 }
-                // Direct function calls: foo()
-                Expr::Call(ExprCall { func, .. }) => {
-                    if let Expr::Path(expr_path) = &**func {
-                        let callee = Self::extract_path_name(&expr_path.path);
-                        self.add_call(&current_fn, &callee);
+                Expr::Call(ExprCall { func, args, .. }) => {
+                    self.visit_expr(func);
+                    for arg in args {
+                        self.visit_expr(arg);
                     }
                 }
-                _ => {}
+                other => self.visit_expr(other),
+            }
+            return;
+        }
+
+        if let Some(current_fn) = self.context.current_function.clone() {
+            self.record_call_expr(&current_fn, node, false);
+        }
+
+        // Plain reassignment (`x = ...;`) to a variable already tracked in scope:
+        // re-infer its type from the new RHS, or drop it back to unknown if the RHS
+        // isn't one of the shapes `infer_init_type` recognizes. This runs regardless
+        // of `current_function`, same as the rest of the locals bookkeeping.
+        if let Expr::Assign(ExprAssign { left, right, .. }) = node {
+            if let Expr::Path(expr_path) = &**left {
+                if let Some(name) = expr_path.path.get_ident() {
+                    self.context
+                        .reassign_local(&name.to_string(), Self::infer_init_type(right));
+                }
             }
         }
 
         // Continue visiting
         syn::visit::visit_expr(self, node);
     }
+
+    // A macro invocation's arguments are an opaque `TokenStream` to `syn`, so the
+    // call-extraction arms in `visit_expr` above never see calls buried inside one
+    // (`println!("{}", compute())`, `vec![make()]`, ...). Re-parse and recurse the
+    // same extraction logic over it via `collect_calls_from_macro`, then stop:
+    // `syn::visit::visit_macro` has nothing further worth walking.
+    fn visit_macro(&mut self, node: &'a Macro) {
+        if let Some(current_fn) = self.context.current_function.clone() {
+            collect_calls_from_macro(self, &current_fn, node);
+        }
+    }
+
+    // A block introduces its own scope: `let` bindings inside it must not leak to
+    // the enclosing block once it ends.
+    fn visit_block(&mut self, node: &'a Block) {
+        self.context.push_scope();
+        syn::visit::visit_block(self, node);
+        self.context.pop_scope();
+    }
+
+    // Closures get their own scope too, seeded with any type-ascribed parameters
+    // (`|x: Type| ...`) so method calls on them resolve the same way a `let`-bound
+    // local would.
+    fn visit_expr_closure(&mut self, node: &'a ExprClosure) {
+        self.context.push_scope();
+        for input in &node.inputs {
+            if let syn::Pat::Type(pat_type) = input {
+                if let (syn::Pat::Ident(pat_ident), syn::Type::Path(type_path)) =
+                    (&*pat_type.pat, &*pat_type.ty)
+                {
+                    self.context
+                        .bind_local(pat_ident.ident.to_string(), Self::extract_path_name(&type_path.path));
+                }
+            }
+        }
+        syn::visit::visit_expr_closure(self, node);
+        self.context.pop_scope();
+    }
+}
+
+/// Flatten one `use` item's tree into `imports`/`import_globs`, expanding leading
+/// `crate`/`self` prefixes. `super` is left as a literal segment — resolving it
+/// requires knowing the importing module's own parent, which this import table
+/// doesn't track; the raw path is still recorded so a glob/name lookup doesn't
+/// silently disappear.
+fn collect_use_tree(
+    tree: &syn::UseTree,
+    mut prefix: Vec<String>,
+    imports: &mut HashMap<String, String>,
+    import_globs: &mut Vec<String>,
+) {
+    match tree {
+        syn::UseTree::Path(use_path) => {
+            prefix.push(use_path.ident.to_string());
+            collect_use_tree(&use_path.tree, prefix, imports, import_globs);
+        }
+        syn::UseTree::Name(use_name) => {
+            let mut full = prefix;
+            full.push(use_name.ident.to_string());
+            imports.insert(use_name.ident.to_string(), normalize_use_path(full));
+        }
+        syn::UseTree::Rename(use_rename) => {
+            let mut full = prefix;
+            full.push(use_rename.ident.to_string());
+            imports.insert(use_rename.rename.to_string(), normalize_use_path(full));
+        }
+        syn::UseTree::Glob(_) => {
+            import_globs.push(normalize_use_path(prefix));
+        }
+        syn::UseTree::Group(use_group) => {
+            for item in &use_group.items {
+                collect_use_tree(item, prefix.clone(), imports, import_globs);
+            }
+        }
+    }
+}
+
+fn normalize_use_path(mut segments: Vec<String>) -> String {
+    if matches!(segments.first().map(String::as_str), Some("crate") | Some("self")) {
+        segments.remove(0);
+    }
+    segments.join("::")
+}
+
+/// Read an item's `#[cfg(...)]` attribute, if it has one, returning the raw token
+/// text inside the parens (e.g. `"test"`, `"not (test)"`, `"feature = \"foo\""). Only
+/// the first `cfg` attribute is consulted — `cfg_attr`/multiple stacked `cfg`s aren't
+/// combined, mirroring how `syn` itself leaves attribute combination to the caller.
+fn extract_cfg_attr(attrs: &[syn::Attribute]) -> Option<String> {
+    attrs.iter().find_map(|attr| {
+        if !attr.path().is_ident("cfg") {
+            return None;
+        }
+        match &attr.meta {
+            syn::Meta::List(list) => Some(list.tokens.to_string()),
+            _ => None,
+        }
+    })
+}
+
+/// A parsed `#[cfg(...)]` predicate, evaluated against an active-cfg set by
+/// [`cfg_predicate_satisfied`]. Parsed from the raw attribute text stored in
+/// [`FunctionMetadata::cfg`]/[`InheritanceInfo::cfg`] rather than kept in parsed
+/// form, so the stored field stays a plain, human-readable string.
+#[derive(Debug, Clone, PartialEq)]
+enum CfgPredicate {
+    Flag(String),
+    KeyValue(String, String),
+    All(Vec<CfgPredicate>),
+    Any(Vec<CfgPredicate>),
+    Not(Box<CfgPredicate>),
+}
+
+/// Split a `cfg(...)` token string into bare identifiers, `(`/`)`/`,`/`=` punctuation,
+/// and quoted string literals, ignoring whitespace — tolerant of however `syn`/
+/// `proc-macro2` chose to space the tokens when rendering them back to text.
+fn tokenize_cfg(input: &str) -> Vec<String> {
+    let chars: Vec<char> = input.chars().collect();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+        if c.is_whitespace() {
+            i += 1;
+        } else if matches!(c, '(' | ')' | ',' | '=') {
+            tokens.push(c.to_string());
+            i += 1;
+        } else if c == '"' {
+            let start = i;
+            i += 1;
+            while i < chars.len() && chars[i] != '"' {
+                i += 1;
+            }
+            i = (i + 1).min(chars.len());
+            tokens.push(chars[start..i].iter().collect());
+        } else if c.is_alphanumeric() || c == '_' {
+            let start = i;
+            while i < chars.len() && (chars[i].is_alphanumeric() || chars[i] == '_') {
+                i += 1;
+            }
+            tokens.push(chars[start..i].iter().collect());
+        } else {
+            i += 1;
+        }
+    }
+
+    tokens
+}
+
+fn expect_token(tokens: &[String], pos: &mut usize, expected: &str) -> Option<()> {
+    if tokens.get(*pos).map(String::as_str) == Some(expected) {
+        *pos += 1;
+        Some(())
+    } else {
+        None
+    }
+}
+
+fn parse_cfg_tokens(tokens: &[String], pos: &mut usize) -> Option<CfgPredicate> {
+    let name = tokens.get(*pos)?.clone();
+    *pos += 1;
+
+    match name.as_str() {
+        "not" => {
+            expect_token(tokens, pos, "(")?;
+            let inner = parse_cfg_tokens(tokens, pos)?;
+            expect_token(tokens, pos, ")")?;
+            Some(CfgPredicate::Not(Box::new(inner)))
+        }
+        "all" | "any" => {
+            expect_token(tokens, pos, "(")?;
+            let mut items = vec![parse_cfg_tokens(tokens, pos)?];
+            while expect_token(tokens, pos, ",").is_some() {
+                items.push(parse_cfg_tokens(tokens, pos)?);
+            }
+            expect_token(tokens, pos, ")")?;
+            Some(if name == "all" { CfgPredicate::All(items) } else { CfgPredicate::Any(items) })
+        }
+        _ if tokens.get(*pos).map(String::as_str) == Some("=") => {
+            *pos += 1;
+            let value = tokens.get(*pos)?.trim_matches('"').to_string();
+            *pos += 1;
+            Some(CfgPredicate::KeyValue(name, value))
+        }
+        _ => Some(CfgPredicate::Flag(name)),
+    }
+}
+
+/// Evaluate `predicate` (as stored in [`FunctionMetadata::cfg`]/
+/// [`InheritanceInfo::cfg`]) against `active_cfg`, an "is this flag/key-value pair
+/// active in the build we're rendering" set (e.g. `{"test"}` or
+/// `{"feature=\"foo\""}`). `None` (no `cfg` attribute at all) always passes.
+pub fn cfg_predicate_satisfied(predicate: &Option<String>, active_cfg: &HashSet<String>) -> bool {
+    let Some(raw) = predicate else {
+        return true;
+    };
+
+    let tokens = tokenize_cfg(raw);
+    let mut pos = 0;
+    match parse_cfg_tokens(&tokens, &mut pos) {
+        Some(parsed) => cfg_node_satisfied(&parsed, active_cfg),
+        // Unparsable predicate: fail open rather than silently hiding the item.
+        None => true,
+    }
+}
+
+fn cfg_node_satisfied(predicate: &CfgPredicate, active_cfg: &HashSet<String>) -> bool {
+    match predicate {
+        CfgPredicate::Flag(name) => active_cfg.contains(name),
+        CfgPredicate::KeyValue(key, value) => active_cfg.contains(&format!("{}=\"{}\"", key, value)),
+        CfgPredicate::All(items) => items.iter().all(|p| cfg_node_satisfied(p, active_cfg)),
+        CfgPredicate::Any(items) => items.iter().any(|p| cfg_node_satisfied(p, active_cfg)),
+        CfgPredicate::Not(inner) => !cfg_node_satisfied(inner, active_cfg),
+    }
+}
+
+/// Resolve call targets in `call_graph` against the import table and the crate's
+/// own function set, now that both are fully populated — this is rust-analyzer's
+/// `nameres` job in miniature. Bare, single-segment callees are resolved whole; for
+/// already-qualified callees (e.g. `Type::method`, recorded by the `visit_expr`
+/// call-site logic) only the first segment is looked up in the imports.
+fn resolve_call_graph(
+    call_graph: &mut HashMap<String, HashSet<(String, bool)>>,
+    functions: &HashMap<String, FunctionMetadata>,
+    imports: &HashMap<String, String>,
+) {
+    for (caller, callees) in call_graph.iter_mut() {
+        let caller_path: Vec<&str> = caller.split("::").collect();
+        *callees = callees
+            .drain()
+            .map(|(callee, awaited)| (resolve_callee(&callee, &caller_path, functions, imports), awaited))
+            .collect();
+    }
+}
+
+/// Resolve one callee name seen from `caller_path` (the calling function's own
+/// fully-qualified name, split on `::`), preferring an explicit `use` import, then
+/// a function declared in the caller's own module or an ancestor module
+/// (innermost first), then falling back to the raw name unchanged.
+fn resolve_callee(
+    callee: &str,
+    caller_path: &[&str],
+    functions: &HashMap<String, FunctionMetadata>,
+    imports: &HashMap<String, String>,
+) -> String {
+    let mut segments: Vec<&str> = callee.split("::").collect();
+    let Some(&first) = segments.first() else {
+        return callee.to_string();
+    };
+
+    // Already fully qualified (e.g. `std::collections::HashMap::new`, recorded
+    // verbatim when the source itself spelled the path out) — nothing left to
+    // resolve through `imports`.
+    if matches!(first, "std" | "core" | "alloc" | "crate" | "self" | "super") {
+        return callee.to_string();
+    }
+
+    // The callee's first segment is the thing a `use` import or alias actually
+    // names (`Type` in `Type::method`, or the bare function itself) — resolve
+    // that segment alone and rejoin with whatever followed it.
+    if let Some(imported) = imports.get(first) {
+        segments[0] = imported.as_str();
+        return segments.join("::");
+    }
+
+    if segments.len() > 1 {
+        return callee.to_string();
+    }
+
+    for depth in (0..caller_path.len()).rev() {
+        let candidate = caller_path[..depth]
+            .iter()
+            .copied()
+            .chain(std::iter::once(callee))
+            .collect::<Vec<_>>()
+            .join("::");
+        if functions.contains_key(&candidate) {
+            return candidate;
+        }
+    }
+
+    callee.to_string()
+}
+
+/// Collapse `inheritance` entries that name the same underlying type/trait but were
+/// spelled differently — a direct path vs. a `use`-imported alias (`use inner::MyType
+/// as M;` then `impl Trait for M`) — into a single entry, the same way
+/// `resolve_call_graph` normalizes call targets through `imports` once the whole
+/// crate's `use` declarations are known. `__trait_def::`-prefixed entries are left
+/// untouched: they're a bookkeeping key consumed (and removed) later in
+/// `extract_relationships`, not a real type/trait pairing.
+fn resolve_inheritance_aliases(
+    inheritance: HashMap<String, InheritanceInfo>,
+    imports: &HashMap<String, String>,
+) -> HashMap<String, InheritanceInfo> {
+    let mut resolved: HashMap<String, InheritanceInfo> = HashMap::new();
+
+    for (key, mut info) in inheritance {
+        if key.starts_with("__trait_def::") {
+            resolved.insert(key, info);
+            continue;
+        }
+
+        if !info.type_name.contains("::") {
+            if let Some(canonical) = imports.get(&info.type_name) {
+                info.type_name = canonical.clone();
+            }
+        }
+
+        if let Some(trait_name) = info.trait_name.take() {
+            let resolved_trait = if !trait_name.contains("::") {
+                imports.get(&trait_name).cloned().unwrap_or(trait_name)
+            } else {
+                trait_name
+            };
+            info.trait_name = Some(resolved_trait);
+        }
+
+        let new_key = match &info.trait_name {
+            Some(trait_name) => format!("{}::{}", info.type_name, trait_name),
+            None => info.type_name.clone(),
+        };
+
+        resolved
+            .entry(new_key)
+            .and_modify(|existing: &mut InheritanceInfo| {
+                for method in &info.methods {
+                    if !existing.methods.contains(method) {
+                        existing.methods.push(method.clone());
+                    }
+                }
+                for (method, cfg) in &info.method_cfgs {
+                    existing.method_cfgs.entry(method.clone()).or_insert_with(|| cfg.clone());
+                }
+            })
+            .or_insert(info);
+    }
+
+    resolved
+}
+
+/// Derive a source file's module path from its location under `src/`, mirroring
+/// how `rustc` itself maps a file to a module: `src/lib.rs`/`src/main.rs` are the
+/// crate root (no prefix), `src/foo.rs` and `src/foo/mod.rs` are both `foo`, and
+/// `src/foo/bar.rs` is `foo::bar`. Every [`RelationshipVisitor`] is seeded with
+/// this path before visiting its file, so the same leaf name declared in two
+/// different modules (two `new`s, two `Config`s) qualifies to distinct keys
+/// instead of one clobbering the other in the merged maps.
+fn module_path_for_file(file_path: &Path) -> Vec<String> {
+    let components: Vec<&str> = file_path
+        .components()
+        .filter_map(|c| c.as_os_str().to_str())
+        .collect();
+
+    let Some(src_index) = components.iter().rposition(|c| *c == "src") else {
+        return Vec::new();
+    };
+
+    let mut path: Vec<String> = components[src_index + 1..].iter().map(|s| s.to_string()).collect();
+
+    if let Some(last) = path.pop() {
+        let stem = last.strip_suffix(".rs").unwrap_or(&last);
+        if stem != "lib" && stem != "main" && stem != "mod" {
+            path.push(stem.to_string());
+        }
+    }
+
+    path
 }
 
 /// Extract relationships from Rust source files
@@ -282,6 +1290,10 @@ pub fn extract_relationships(source_files: Vec<PathBuf>) -> CodeRelationships {
     let mut call_graph = HashMap::new();
     let mut inheritance = HashMap::new();
     let mut functions = HashMap::new();
+    let mut verus_contracts = HashMap::new();
+    let mut cfgs = HashMap::new();
+    let mut imports = HashMap::new();
+    let mut import_globs = Vec::new();
 
     for file_path in source_files {
         if let Ok(content) = std::fs::read_to_string(&file_path) {
@@ -290,21 +1302,37 @@ pub fn extract_relationships(source_files: Vec<PathBuf>) -> CodeRelationships {
                     &mut call_graph,
                     &mut inheritance,
                     &mut functions,
+                    &mut cfgs,
+                    &mut imports,
+                    &mut import_globs,
                     file_path.clone(),
                 );
+                visitor.context.module_path = module_path_for_file(&file_path);
                 visitor.visit_file(&ast);
             }
+
+            verus_contracts.extend(crate::verus_contracts::extract_verus_contracts(&content));
         }
     }
 
+    // Now that every file's functions and imports are known, resolve bare call
+    // targets (a use-imported name, or a sibling in the caller's own/ancestor
+    // module) to their fully-qualified key in `functions`.
+    resolve_call_graph(&mut call_graph, &functions, &imports);
+
+    // Likewise, collapse impls that name the same type/trait through different
+    // spellings (a direct path vs. a `use`-imported alias) before anything keys
+    // off `inheritance` entries.
+    let mut inheritance = resolve_inheritance_aliases(inheritance, &imports);
+
     // Build usage graph (reverse call graph)
-    let mut usage_graph: HashMap<String, HashSet<String>> = HashMap::new();
+    let mut usage_graph: HashMap<String, HashSet<(String, bool)>> = HashMap::new();
     for (caller, callees) in &call_graph {
-        for callee in callees {
+        for (callee, awaited) in callees {
             usage_graph
                 .entry(callee.clone())
                 .or_insert_with(HashSet::new)
-                .insert(caller.clone());
+                .insert((caller.clone(), *awaited));
         }
     }
 
@@ -335,66 +1363,149 @@ pub fn extract_relationships(source_files: Vec<PathBuf>) -> CodeRelationships {
         call_graph,
         usage_graph,
         inheritance,
+        trait_supertraits: trait_definitions,
         functions,
+        verus_contracts,
+        cfgs,
     }
 }
 
-/// Generate an SVG inheritance/trait implementation graph for a specific type
-pub fn generate_type_inheritance_graph(
-    type_name: &str,
-    relationships: &CodeRelationships,
-) -> Option<String> {
-    // Find all trait implementations for this type
-    let trait_impls: Vec<(&String, &InheritanceInfo)> = relationships
-        .inheritance
-        .iter()
-        .filter(|(_, info)| info.type_name == type_name && info.trait_name.is_some())
-        .collect();
+/// Resolve the rustdoc page (and anchor, for methods) that documents `name`, if it is
+/// an item we extracted from the crate's own sources. Returns `None` for anything
+/// external (stdlib calls, third-party crates, etc.) so callers can render a plain,
+/// non-linked node for it.
+///
+/// The href is relative to the directory a page for `name` would itself live in.
+/// `name` is now module-qualified (see `Context::module_path`/`module_path_for_file`),
+/// but rustdoc itself lays nested-module pages out under a matching directory tree
+/// rather than flattening them, so a real "../" prefix for `mod`-nested items still
+/// needs that directory layout threaded in here — left as a flat href for now.
+pub fn resolve_doc_href(name: &str, relationships: &CodeRelationships) -> Option<String> {
+    let simple_name = name.split("::").last().unwrap_or(name);
+
+    if let Some(metadata) = relationships.functions.get(name) {
+        return if metadata.is_method {
+            let parent = metadata.parent_type.as_ref()?;
+            let simple_parent = parent.split("::").last().unwrap_or(parent);
+            Some(format!(
+                "struct.{}.html#method.{}",
+                simple_parent, simple_name
+            ))
+        } else {
+            Some(format!("fn.{}.html", simple_name))
+        };
+    }
 
-    // Also check for inherent impl (no trait)
-    let inherent_impl = relationships
+    if relationships
         .inheritance
-        .get(type_name)
-        .filter(|info| info.trait_name.is_none());
+        .values()
+        .any(|info| info.type_name == name)
+    {
+        return Some(format!("struct.{}.html", simple_name));
+    }
 
-    if trait_impls.is_empty() && inherent_impl.is_none() {
-        return None;
+    if relationships.trait_supertraits.contains_key(name)
+        || relationships
+            .inheritance
+            .values()
+            .any(|info| info.trait_name.as_deref() == Some(name))
+    {
+        return Some(format!("trait.{}.html", simple_name));
     }
 
-    // If only inherent impl exists (no traits), generate simple diagram
-    if trait_impls.is_empty() {
-        let width = 400;
-        let height = 200;
-        let simple_type = type_name.split("::").last().unwrap_or(type_name);
+    None
+}
 
-        let mut svg = format!(
-            "<svg width=\"{}\" height=\"{}\" xmlns=\"http://www.w3.org/2000/svg\">\n  \
-  <style>\n    \
-    .type-node {{ fill: rgb(33, 150, 243); stroke: rgb(21, 101, 192); stroke-width: 3; }}\n    \
-    .text {{ fill: white; font-family: monospace; font-size: 12px; font-weight: bold; text-anchor: middle; }}\n    \
-    .method-text {{ fill: white; font-family: monospace; font-size: 10px; text-anchor: middle; opacity: 0.9; }}\n  \
-  </style>\n",
-            width, height
-        );
+/// Resolve `name`'s doc-page href for use as a node's hyperlink, suppressing the
+/// link when it would just point back at the page already being rendered.
+fn node_href(name: &str, relationships: &CodeRelationships, current_file: &Path) -> Option<String> {
+    let current_page = current_file
+        .file_name()
+        .and_then(|f| f.to_str())
+        .unwrap_or("");
 
-        svg.push_str(&format!(
-            "  <rect x=\"50\" y=\"75\" width=\"300\" height=\"70\" rx=\"5\" class=\"type-node\" />\n"
-        ));
-        svg.push_str(&format!(
-            "  <text x=\"200\" y=\"105\" class=\"text\">{}</text>\n",
-            simple_type
-        ));
-        svg.push_str(&format!(
-            "  <text x=\"200\" y=\"127\" class=\"method-text\">struct (no traits)</text>\n"
-        ));
-        svg.push_str("</svg>");
+    resolve_doc_href(name, relationships).filter(|href| href.split('#').next() != Some(current_page))
+}
+
+/// The short label a node should render: `name`'s last path segment, unless some
+/// other known function or type shares that same last segment (two modules each
+/// with their own `Config` or `new`), in which case one more segment — the
+/// module tail — is kept to tell them apart, e.g. `net::Config` vs `db::Config`.
+fn disambiguated_label(name: &str, relationships: &CodeRelationships) -> String {
+    let short = name.rsplit("::").next().unwrap_or(name);
+
+    let collides = relationships
+        .functions
+        .keys()
+        .map(String::as_str)
+        .chain(relationships.inheritance.values().map(|info| info.type_name.as_str()))
+        .any(|other| other != name && other.rsplit("::").next() == Some(short));
+
+    if !collides {
+        return short.to_string();
+    }
 
-        return Some(svg);
+    let segments: Vec<&str> = name.split("::").collect();
+    if segments.len() >= 2 {
+        segments[segments.len() - 2..].join("::")
+    } else {
+        short.to_string()
+    }
+}
+
+/// Tooltip text for a node's hover `<title>`: its full method list, one per line
+/// (this crate doesn't track method signatures, so names are all there is to show).
+fn method_tooltip(methods: &[String]) -> String {
+    if methods.is_empty() {
+        "no methods".to_string()
+    } else {
+        methods.join("\n")
+    }
+}
+
+/// Same as [`method_tooltip`], but appends each method's own cfg predicate (from
+/// [`InheritanceInfo::method_cfgs`]) when it doesn't hold under `active_cfg`, so a
+/// hover on a [`generate_type_inheritance_graph_cfg`] node shows exactly which
+/// methods are configuration-gated out rather than just the method list.
+fn method_tooltip_cfg(info: &InheritanceInfo, active_cfg: &HashSet<String>) -> String {
+    if info.methods.is_empty() {
+        return "no methods".to_string();
     }
 
+    info.methods
+        .iter()
+        .map(|method| {
+            let cfg = info.method_cfgs.get(method).and_then(|c| c.as_deref());
+            if cfg_predicate_satisfied(&cfg.map(str::to_string), active_cfg) {
+                method.clone()
+            } else {
+                format!("{} [cfg({})]", method, cfg.unwrap_or("inactive"))
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// The hierarchical grid layout shared by [`generate_type_inheritance_graph`] and
+/// [`generate_type_inheritance_graph_cfg`]: trait nodes positioned by supertrait
+/// depth (layer 0 = root traits, increasing left-to-right), the type node pinned to
+/// the right edge, and the overall canvas sized to fit the widest layer. Node
+/// coloring and labels are the only thing that differs between the two graph
+/// variants, so they're computed separately by each caller.
+struct TraitLayout {
+    width: usize,
+    height: usize,
+    node_width: usize,
+    node_height: usize,
+    type_x: usize,
+    type_y: usize,
+    trait_positions: HashMap<String, (usize, usize)>,
+}
+
+fn compute_trait_layout(trait_impls: &[(&String, &InheritanceInfo)]) -> TraitLayout {
     // Build dependency graph: child -> parents
     let mut dependencies: HashMap<String, Vec<String>> = HashMap::new();
-    for (_, info) in &trait_impls {
+    for (_, info) in trait_impls {
         let trait_name = info.trait_name.as_ref().unwrap().clone();
         dependencies.insert(trait_name, info.parent_traits.clone());
     }
@@ -429,7 +1540,7 @@ pub fn generate_type_inheritance_graph(
     }
 
     let mut trait_layers: HashMap<String, usize> = HashMap::new();
-    for (_, info) in &trait_impls {
+    for (_, info) in trait_impls {
         let trait_name = info.trait_name.as_ref().unwrap();
         calculate_layer(trait_name, &dependencies, &mut trait_layers);
     }
@@ -482,59 +1593,164 @@ pub fn generate_type_inheritance_graph(
     let type_x = width - 350;
     let type_y = height / 2 - 35;
 
-    // Generate SVG
-    let mut svg = format!(
-        "<svg width=\"{}\" height=\"{}\" xmlns=\"http://www.w3.org/2000/svg\">\n  \
-  <style>\n    \
-    .type-node {{ fill: rgb(33, 150, 243); stroke: rgb(21, 101, 192); stroke-width: 3; }}\n    \
-    .trait-node {{ fill: rgb(156, 39, 176); stroke: rgb(106, 27, 154); stroke-width: 2; }}\n    \
-    .impl-edge {{ stroke: rgb(156, 39, 176); stroke-width: 3; marker-end: url(#impl-arrow); }}\n    \
-    .super-edge {{ stroke: rgb(255, 152, 0); stroke-width: 2; stroke-dasharray: 6,4; marker-end: url(#super-arrow); }}\n    \
-    .text {{ fill: white; font-family: monospace; font-size: 12px; font-weight: bold; text-anchor: middle; }}\n    \
-    .method-text {{ fill: white; font-family: monospace; font-size: 10px; text-anchor: middle; opacity: 0.9; }}\n  \
-  </style>\n  \
-  <defs>\n    \
-    <marker id=\"impl-arrow\" markerWidth=\"12\" markerHeight=\"12\" refX=\"10\" refY=\"3\" orient=\"auto\">\n      \
-      <polygon points=\"0 0, 12 3, 0 6\" fill=\"rgb(156, 39, 176)\" />\n    \
-    </marker>\n    \
-    <marker id=\"super-arrow\" markerWidth=\"12\" markerHeight=\"12\" refX=\"10\" refY=\"3\" orient=\"auto\">\n      \
-      <polygon points=\"0 0, 12 3, 0 6\" fill=\"rgb(255, 152, 0)\" />\n    \
-    </marker>\n  \
-  </defs>\n",
-        width, height
-    );
-
-    // DRAW ARROWS FIRST (so they appear below/behind nodes)
-
-    // Supertrait arrows (parent -> child, flowing left-to-right)
-    let supertraits: HashSet<String> = trait_impls
-        .iter()
-        .flat_map(|(_, info)| info.parent_traits.iter().cloned())
-        .collect();
+    TraitLayout {
+        width,
+        height,
+        node_width,
+        node_height,
+        type_x,
+        type_y,
+        trait_positions,
+    }
+}
 
-    for (_, info) in trait_impls.iter() {
+/// Supertrait arrows (child -> parent, flowing right-to-left): drawn identically by
+/// both graph variants, since which traits extend which doesn't depend on cfg state.
+fn draw_supertrait_arrows(doc: &mut Document, trait_impls: &[(&String, &InheritanceInfo)], layout: &TraitLayout) {
+    for (_, info) in trait_impls {
         let child_trait = info.trait_name.as_ref().unwrap();
-        if let Some((child_x, child_y)) = trait_positions.get(child_trait) {
+        if let Some((child_x, child_y)) = layout.trait_positions.get(child_trait) {
             for parent_trait in &info.parent_traits {
-                if let Some((parent_x, parent_y)) = trait_positions.get(parent_trait) {
+                if let Some((parent_x, parent_y)) = layout.trait_positions.get(parent_trait) {
                     // parent_traits means "these are my PARENTS/supertraits"
                     // So arrow should flow FROM child (who has the parents) TO parent
                     // This represents the "extends" relationship: child extends parent
                     // Arrow flows child→parent (RIGHT to LEFT for hierarchy display)
                     // START at CENTER of child, END at RIGHT EDGE of parent
-                    let start_x = child_x + node_width / 2; // Center of child
-                    let start_y = child_y + node_height / 2;
-                    let end_x = parent_x + node_width; // Right edge of parent
-                    let end_y = parent_y + node_height / 2;
-
-                    svg.push_str(&format!(
-                        "  <line x1=\"{}\" y1=\"{}\" x2=\"{}\" y2=\"{}\" class=\"super-edge\" />\n",
-                        start_x, start_y, end_x, end_y
-                    ));
+                    let start_x = child_x + layout.node_width / 2; // Center of child
+                    let start_y = child_y + layout.node_height / 2;
+                    let end_x = parent_x + layout.node_width; // Right edge of parent
+                    let end_y = parent_y + layout.node_height / 2;
+
+                    doc.add_edge(
+                        start_x as f64,
+                        start_y as f64,
+                        end_x as f64,
+                        end_y as f64,
+                        "super-edge",
+                        &format!("trait {}", child_trait.split("::").last().unwrap_or(child_trait)),
+                        &format!("trait {}", parent_trait.split("::").last().unwrap_or(parent_trait)),
+                    );
                 }
             }
         }
     }
+}
+
+/// Interactive features [`generate_type_inheritance_graph`] can opt into, in the
+/// spirit of rust-analyzer's hover actions and rustdoc's generated HTML: clicking a
+/// node to jump to that item's own doc page, and hovering one for its method list.
+#[derive(Debug, Clone, Copy)]
+pub struct GraphOptions {
+    /// Wrap each trait-node/type-node in an `<a href>` to that item's own doc page
+    /// (suppressed for the page already being rendered), so a folder of generated
+    /// graphs becomes click-through navigable instead of a dead-end image.
+    pub links: bool,
+    /// Attach an SVG `<title>` child — the node's method list — to every
+    /// trait-node/type-node, for on-hover tooltips.
+    pub tooltips: bool,
+}
+
+impl Default for GraphOptions {
+    fn default() -> Self {
+        GraphOptions { links: true, tooltips: true }
+    }
+}
+
+/// Generate an SVG inheritance/trait implementation graph for a specific type,
+/// restricted to the `impl` blocks whose `#[cfg(...)]` predicate (if any) is
+/// satisfied by `active_cfg` — an empty set renders the no-flags configuration
+/// (unconditional impls plus those whose predicate holds with no flags, e.g.
+/// `not(test)`), or populate it (e.g. with `"test"`) to render a specific configuration.
+pub fn generate_type_inheritance_graph(
+    type_name: &str,
+    relationships: &CodeRelationships,
+    current_file: &Path,
+    active_cfg: &HashSet<String>,
+    options: GraphOptions,
+) -> Option<String> {
+    // Find all trait implementations for this type
+    let trait_impls: Vec<(&String, &InheritanceInfo)> = relationships
+        .inheritance
+        .iter()
+        .filter(|(_, info)| {
+            info.type_name == type_name
+                && info.trait_name.is_some()
+                && cfg_predicate_satisfied(&info.cfg, active_cfg)
+        })
+        .collect();
+
+    // Also check for inherent impl (no trait)
+    let inherent_impl = relationships
+        .inheritance
+        .get(type_name)
+        .filter(|info| info.trait_name.is_none() && cfg_predicate_satisfied(&info.cfg, active_cfg));
+
+    if trait_impls.is_empty() && inherent_impl.is_none() {
+        return None;
+    }
+
+    // If only inherent impl exists (no traits), generate simple diagram
+    if trait_impls.is_empty() {
+        let mut doc = Document::new(400, 200);
+        doc.set_style(
+            "    .type-node { fill: rgb(33, 150, 243); stroke: rgb(21, 101, 192); stroke-width: 3; }\n    \
+    .text { fill: white; font-family: monospace; font-size: 12px; font-weight: bold; text-anchor: middle; }\n    \
+    .method-text { fill: white; font-family: monospace; font-size: 10px; text-anchor: middle; opacity: 0.9; }"
+                .to_string(),
+        );
+
+        let simple_type = disambiguated_label(type_name, relationships);
+        let href = options.links.then(|| node_href(type_name, relationships, current_file)).flatten();
+        let type_node = doc.add_linked_rect(50.0, 75.0, 300.0, 70.0, 5.0, "type-node", type_name, href.as_deref());
+        if options.tooltips {
+            if let Some(info) = inherent_impl {
+                doc.set_title(type_node, method_tooltip(&info.methods));
+            }
+        }
+        doc.add_text(200.0, 105.0, &simple_type, "text");
+        doc.add_text(200.0, 127.0, "struct (no traits)", "method-text");
+
+        return Some(doc.to_svg());
+    }
+
+    let layout = compute_trait_layout(&trait_impls);
+    let TraitLayout { width, height, node_width, node_height, type_x, type_y, ref trait_positions } = layout;
+
+    let mut doc = Document::new(width as u32, height as u32);
+    doc.set_style(
+        "    .type-node { fill: rgb(33, 150, 243); stroke: rgb(21, 101, 192); stroke-width: 3; }\n    \
+    .trait-node { fill: rgb(156, 39, 176); stroke: rgb(106, 27, 154); stroke-width: 2; }\n    \
+    .impl-edge { stroke: rgb(156, 39, 176); stroke-width: 3; marker-end: url(#impl-arrow); }\n    \
+    .super-edge { stroke: rgb(255, 152, 0); stroke-width: 2; stroke-dasharray: 6,4; marker-end: url(#super-arrow); }\n    \
+    .blanket-edge { stroke: rgb(76, 175, 80); stroke-width: 3; stroke-dasharray: 2,3; marker-end: url(#blanket-arrow); }\n    \
+    .text { fill: white; font-family: monospace; font-size: 12px; font-weight: bold; text-anchor: middle; }\n    \
+    .method-text { fill: white; font-family: monospace; font-size: 10px; text-anchor: middle; opacity: 0.9; }\n    \
+    .bound-text { fill: rgb(76, 175, 80); font-family: monospace; font-size: 10px; text-anchor: middle; font-style: italic; }"
+            .to_string(),
+    );
+    doc.set_defs(
+        "    <marker id=\"impl-arrow\" markerWidth=\"12\" markerHeight=\"12\" refX=\"10\" refY=\"3\" orient=\"auto\">\n      \
+      <polygon points=\"0 0, 12 3, 0 6\" fill=\"rgb(156, 39, 176)\" />\n    \
+    </marker>\n    \
+    <marker id=\"super-arrow\" markerWidth=\"12\" markerHeight=\"12\" refX=\"10\" refY=\"3\" orient=\"auto\">\n      \
+      <polygon points=\"0 0, 12 3, 0 6\" fill=\"rgb(255, 152, 0)\" />\n    \
+    </marker>\n    \
+    <marker id=\"blanket-arrow\" markerWidth=\"12\" markerHeight=\"12\" refX=\"10\" refY=\"3\" orient=\"auto\">\n      \
+      <polygon points=\"0 0, 12 3, 0 6\" fill=\"rgb(76, 175, 80)\" />\n    \
+    </marker>"
+            .to_string(),
+    );
+
+    let type_label = disambiguated_label(type_name, relationships);
+
+    // DRAW ARROWS FIRST (so they appear below/behind nodes)
+
+    let supertraits: HashSet<String> = trait_impls
+        .iter()
+        .flat_map(|(_, info)| info.parent_traits.iter().cloned())
+        .collect();
+    draw_supertrait_arrows(&mut doc, &trait_impls, &layout);
 
     // Implementation arrows (leaf trait -> type, flowing left-to-right)
     for (_, info) in trait_impls.iter() {
@@ -552,10 +1768,17 @@ pub fn generate_type_inheritance_graph(
             let end_x = type_x; // Left edge of type node
             let end_y = type_y + 35; // Center of type node
 
-            svg.push_str(&format!(
-                "  <line x1=\"{}\" y1=\"{}\" x2=\"{}\" y2=\"{}\" class=\"impl-edge\" />\n",
-                start_x, start_y, end_x, end_y
-            ));
+            let edge_class = if info.is_blanket { "blanket-edge" } else { "impl-edge" };
+
+            doc.add_edge(
+                start_x as f64,
+                start_y as f64,
+                end_x as f64,
+                end_y as f64,
+                edge_class,
+                &format!("trait {}", trait_name.split("::").last().unwrap_or(trait_name)),
+                &type_label,
+            );
         }
     }
 
@@ -567,17 +1790,27 @@ pub fn generate_type_inheritance_graph(
         if let Some((x, y)) = trait_positions.get(trait_name) {
             let simple_trait = trait_name.split("::").last().unwrap_or(trait_name);
 
-            svg.push_str(&format!(
-                "  <rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" rx=\"5\" class=\"trait-node\" />\n",
-                x, y, node_width, node_height
-            ));
-
-            svg.push_str(&format!(
-                "  <text x=\"{}\" y=\"{}\" class=\"text\">trait {}</text>\n",
-                x + node_width / 2,
-                y + 25,
-                simple_trait
-            ));
+            let trait_href = options.links.then(|| node_href(trait_name, relationships, current_file)).flatten();
+            let trait_node = doc.add_linked_rect(
+                *x as f64,
+                *y as f64,
+                node_width as f64,
+                node_height as f64,
+                5.0,
+                "trait-node",
+                trait_name,
+                trait_href.as_deref(),
+            );
+            if options.tooltips {
+                doc.set_title(trait_node, method_tooltip(&info.methods));
+            }
+            doc.add_linked_text(
+                (x + node_width / 2) as f64,
+                (y + 25) as f64,
+                &format!("trait {}", simple_trait),
+                "text",
+                trait_href.as_deref(),
+            );
 
             let methods_str = if info.methods.is_empty() {
                 "no methods".to_string()
@@ -587,152 +1820,961 @@ pub fn generate_type_inheritance_graph(
                 format!("{} methods", info.methods.len())
             };
 
-            svg.push_str(&format!(
-                "  <text x=\"{}\" y=\"{}\" class=\"method-text\">{}</text>\n",
-                x + node_width / 2,
-                y + 50,
-                methods_str
-            ));
+            doc.add_text((x + node_width / 2) as f64, (y + 50) as f64, &methods_str, "method-text");
+
+            if info.is_blanket && !info.bounds.is_empty() {
+                doc.add_text(
+                    (x + node_width / 2) as f64,
+                    (y + 65) as f64,
+                    &format!("where {}", info.bounds.join(", ")),
+                    "bound-text",
+                );
+            }
         }
     }
 
     // Draw type node
-    let simple_type = type_name.split("::").last().unwrap_or(type_name);
-    svg.push_str(&format!(
-        "  <rect x=\"{}\" y=\"{}\" width=\"300\" height=\"70\" rx=\"5\" class=\"type-node\" />\n",
-        type_x, type_y
-    ));
-    svg.push_str(&format!(
-        "  <text x=\"{}\" y=\"{}\" class=\"text\">{}</text>\n",
-        type_x + 150,
-        type_y + 30,
-        simple_type
-    ));
-    svg.push_str(&format!(
-        "  <text x=\"{}\" y=\"{}\" class=\"method-text\">struct</text>\n",
-        type_x + 150,
-        type_y + 52
-    ));
-
-    svg.push_str("</svg>");
+    let href = options.links.then(|| node_href(type_name, relationships, current_file)).flatten();
+    let type_node = doc.add_linked_rect(
+        type_x as f64,
+        type_y as f64,
+        300.0,
+        70.0,
+        5.0,
+        "type-node",
+        type_name,
+        href.as_deref(),
+    );
+    if options.tooltips {
+        let all_methods: Vec<String> = inherent_impl
+            .map(|info| info.methods.clone())
+            .into_iter()
+            .chain(trait_impls.iter().map(|(_, info)| info.methods.clone()))
+            .flatten()
+            .collect();
+        doc.set_title(type_node, method_tooltip(&all_methods));
+    }
+    doc.add_linked_text((type_x + 150) as f64, (type_y + 30) as f64, &type_label, "text", href.as_deref());
+    doc.add_text((type_x + 150) as f64, (type_y + 52) as f64, "struct", "method-text");
 
-    Some(svg)
+    Some(doc.to_svg())
 }
 
-/// Generate a simple SVG call graph for a specific function
-pub fn generate_function_call_graph(
-    function_name: &str,
+/// Same layout as [`generate_type_inheritance_graph`], but instead of hiding `impl`
+/// blocks whose `#[cfg(...)]` predicate doesn't hold under `active_cfg`, draws them
+/// greyed-out with their predicate as a label (a method's own predicate is shown in
+/// its node's hover tooltip, not drawn greyed-out) — so a type's trait surface
+/// under two different configurations (say, `test` vs. release) can be compared on
+/// one graph instead of switching between two filtered ones.
+pub fn generate_type_inheritance_graph_cfg(
+    type_name: &str,
     relationships: &CodeRelationships,
+    current_file: &Path,
+    active_cfg: &HashSet<String>,
+    options: GraphOptions,
 ) -> Option<String> {
-    // Check if function exists
-    if !relationships.functions.contains_key(function_name) {
+    let trait_impls: Vec<(&String, &InheritanceInfo)> = relationships
+        .inheritance
+        .iter()
+        .filter(|(_, info)| info.type_name == type_name && info.trait_name.is_some())
+        .collect();
+
+    let inherent_impl = relationships
+        .inheritance
+        .get(type_name)
+        .filter(|info| info.trait_name.is_none());
+
+    if trait_impls.is_empty() && inherent_impl.is_none() {
         return None;
     }
 
-    let callees = relationships
-        .call_graph
-        .get(function_name)
-        .map(|set| set.iter().cloned().collect::<Vec<_>>())
-        .unwrap_or_default();
+    // Same layering as the plain variant — trait positions don't depend on cfg state.
+    let layout = compute_trait_layout(&trait_impls);
+    let TraitLayout { width, height, node_width, node_height, type_x, type_y, ref trait_positions } = layout;
+
+    let mut doc = Document::new(width as u32, height as u32);
+    doc.set_style(
+        "    .type-node { fill: rgb(33, 150, 243); stroke: rgb(21, 101, 192); stroke-width: 3; }\n    \
+    .type-node-inactive { fill: rgb(189, 189, 189); stroke: rgb(117, 117, 117); stroke-width: 3; }\n    \
+    .trait-node { fill: rgb(156, 39, 176); stroke: rgb(106, 27, 154); stroke-width: 2; }\n    \
+    .trait-node-inactive { fill: rgb(189, 189, 189); stroke: rgb(117, 117, 117); stroke-width: 2; }\n    \
+    .impl-edge { stroke: rgb(156, 39, 176); stroke-width: 3; marker-end: url(#impl-arrow); }\n    \
+    .impl-edge-inactive { stroke: rgb(189, 189, 189); stroke-width: 2; stroke-dasharray: 3,3; marker-end: url(#impl-arrow-inactive); }\n    \
+    .super-edge { stroke: rgb(255, 152, 0); stroke-width: 2; stroke-dasharray: 6,4; marker-end: url(#super-arrow); }\n    \
+    .blanket-edge { stroke: rgb(76, 175, 80); stroke-width: 3; stroke-dasharray: 2,3; marker-end: url(#blanket-arrow); }\n    \
+    .text { fill: white; font-family: monospace; font-size: 12px; font-weight: bold; text-anchor: middle; }\n    \
+    .method-text { fill: white; font-family: monospace; font-size: 10px; text-anchor: middle; opacity: 0.9; }\n    \
+    .bound-text { fill: rgb(76, 175, 80); font-family: monospace; font-size: 10px; text-anchor: middle; font-style: italic; }\n    \
+    .cfg-label-text { fill: rgb(97, 97, 97); font-family: monospace; font-size: 10px; text-anchor: middle; font-style: italic; }"
+            .to_string(),
+    );
+    doc.set_defs(
+        "    <marker id=\"impl-arrow\" markerWidth=\"12\" markerHeight=\"12\" refX=\"10\" refY=\"3\" orient=\"auto\">\n      \
+      <polygon points=\"0 0, 12 3, 0 6\" fill=\"rgb(156, 39, 176)\" />\n    \
+    </marker>\n    \
+    <marker id=\"impl-arrow-inactive\" markerWidth=\"12\" markerHeight=\"12\" refX=\"10\" refY=\"3\" orient=\"auto\">\n      \
+      <polygon points=\"0 0, 12 3, 0 6\" fill=\"rgb(189, 189, 189)\" />\n    \
+    </marker>\n    \
+    <marker id=\"super-arrow\" markerWidth=\"12\" markerHeight=\"12\" refX=\"10\" refY=\"3\" orient=\"auto\">\n      \
+      <polygon points=\"0 0, 12 3, 0 6\" fill=\"rgb(255, 152, 0)\" />\n    \
+    </marker>\n    \
+    <marker id=\"blanket-arrow\" markerWidth=\"12\" markerHeight=\"12\" refX=\"10\" refY=\"3\" orient=\"auto\">\n      \
+      <polygon points=\"0 0, 12 3, 0 6\" fill=\"rgb(76, 175, 80)\" />\n    \
+    </marker>"
+            .to_string(),
+    );
 
-    let callers = relationships
-        .usage_graph
-        .get(function_name)
-        .map(|set| set.iter().cloned().collect::<Vec<_>>())
-        .unwrap_or_default();
+    let type_label = disambiguated_label(type_name, relationships);
+    let type_active = inherent_impl.is_none_or(|info| cfg_predicate_satisfied(&info.cfg, active_cfg));
 
-    if callees.is_empty() && callers.is_empty() {
-        return None;
-    }
+    let supertraits: HashSet<String> =
+        trait_impls.iter().flat_map(|(_, info)| info.parent_traits.iter().cloned()).collect();
 
-    // Simple vertical layout
-    let width = 800;
-    let height = 200 + (callees.len().max(callers.len()) * 40);
-    let center_x = width / 2;
-    let center_y = height / 2;
+    draw_supertrait_arrows(&mut doc, &trait_impls, &layout);
 
-    let mut svg = format!(
-        "<svg width=\"{}\" height=\"{}\" xmlns=\"http://www.w3.org/2000/svg\">\n  \
-  <style>\n    \
-    .node {{ fill: rgb(76, 175, 80); stroke: rgb(46, 125, 50); stroke-width: 2; }}\n    \
-    .current {{ fill: rgb(33, 150, 243); stroke: rgb(21, 101, 192); stroke-width: 3; }}\n    \
-    .caller {{ fill: rgb(255, 193, 7); stroke: rgb(245, 124, 0); stroke-width: 2; }}\n    \
-    .edge {{ stroke: rgb(102, 102, 102); stroke-width: 2; marker-end: url(#arrowhead); }}\n    \
-    .caller-edge {{ stroke: rgb(245, 124, 0); stroke-width: 2; marker-end: url(#arrowhead); }}\n    \
-    .text {{ fill: white; font-family: monospace; font-size: 12px; text-anchor: middle; }}\n  \
-  </style>\n  \
-  <defs>\n    \
-    <marker id=\"arrowhead\" markerWidth=\"10\" markerHeight=\"10\" refX=\"9\" refY=\"3\" orient=\"auto\">\n      \
-      <polygon points=\"0 0, 10 3, 0 6\" fill=\"rgb(102, 102, 102)\" />\n    \
-    </marker>\n  \
-  </defs>\n",
-        width, height
-    );
+    for (_, info) in trait_impls.iter() {
+        let trait_name = info.trait_name.as_ref().unwrap();
+        if supertraits.contains(trait_name) {
+            continue;
+        }
 
-    // Draw edges from callers to current function
-    for (i, _caller) in callers.iter().enumerate() {
-        let y = 50 + i * 40;
-        svg.push_str(&format!(
-            "  <line x1=\"150\" y1=\"{}\" x2=\"{}\" y2=\"{}\" class=\"caller-edge\" />\n",
-            y + 15,
-            center_x - 120,
-            center_y + 15
-        ));
-    }
+        if let Some((trait_x, trait_y)) = trait_positions.get(trait_name) {
+            let start_x = trait_x + node_width / 2;
+            let start_y = trait_y + node_height / 2;
+            let end_x = type_x;
+            let end_y = type_y + 35;
+
+            let impl_active = cfg_predicate_satisfied(&info.cfg, active_cfg);
+            let edge_class = if !impl_active {
+                "impl-edge-inactive"
+            } else if info.is_blanket {
+                "blanket-edge"
+            } else {
+                "impl-edge"
+            };
 
-    // Draw edges from current function to callees
-    for (i, _callee) in callees.iter().enumerate() {
-        let y = 50 + i * 40;
-        svg.push_str(&format!(
-            "  <line x1=\"{}\" y1=\"{}\" x2=\"{}\" y2=\"{}\" class=\"edge\" />\n",
-            center_x + 120,
-            center_y + 15,
-            width - 150,
-            y + 15
-        ));
+            doc.add_edge(
+                start_x as f64,
+                start_y as f64,
+                end_x as f64,
+                end_y as f64,
+                edge_class,
+                &format!("trait {}", trait_name.split("::").last().unwrap_or(trait_name)),
+                &type_label,
+            );
+        }
     }
 
-    // Draw caller nodes
-    for (i, caller) in callers.iter().enumerate() {
-        let y = 50 + i * 40;
-        let label = caller.split("::").last().unwrap_or(caller);
-        svg.push_str(&format!(
-            "  <rect x=\"20\" y=\"{}\" width=\"260\" height=\"30\" rx=\"5\" class=\"caller\" />\n  \
-  <text x=\"150\" y=\"{}\" class=\"text\">{}</text>\n",
-            y,
-            y + 20,
-            label
-        ));
+    for (_, info) in trait_impls.iter() {
+        let trait_name = info.trait_name.as_ref().unwrap();
+        if let Some((x, y)) = trait_positions.get(trait_name) {
+            let simple_trait = trait_name.split("::").last().unwrap_or(trait_name);
+            let impl_active = cfg_predicate_satisfied(&info.cfg, active_cfg);
+            let node_class = if impl_active { "trait-node" } else { "trait-node-inactive" };
+
+            let trait_href = options.links.then(|| node_href(trait_name, relationships, current_file)).flatten();
+            let trait_node = doc.add_linked_rect(
+                *x as f64,
+                *y as f64,
+                node_width as f64,
+                node_height as f64,
+                5.0,
+                node_class,
+                trait_name,
+                trait_href.as_deref(),
+            );
+            if options.tooltips {
+                doc.set_title(trait_node, method_tooltip_cfg(info, active_cfg));
+            }
+            doc.add_linked_text(
+                (x + node_width / 2) as f64,
+                (y + 25) as f64,
+                &format!("trait {}", simple_trait),
+                "text",
+                trait_href.as_deref(),
+            );
+
+            let methods_str = if info.methods.is_empty() {
+                "no methods".to_string()
+            } else if info.methods.len() <= 2 {
+                info.methods.join(", ")
+            } else {
+                format!("{} methods", info.methods.len())
+            };
+
+            doc.add_text((x + node_width / 2) as f64, (y + 50) as f64, &methods_str, "method-text");
+
+            let label = if !impl_active {
+                info.cfg.as_deref().map(|predicate| format!("cfg({})", predicate))
+            } else if info.is_blanket && !info.bounds.is_empty() {
+                Some(format!("where {}", info.bounds.join(", ")))
+            } else {
+                None
+            };
+            if let Some(label) = label {
+                let class = if impl_active { "bound-text" } else { "cfg-label-text" };
+                doc.add_text((x + node_width / 2) as f64, (y + 65) as f64, &label, class);
+            }
+        }
     }
 
-    // Draw current function node
-    let label = function_name.split("::").last().unwrap_or(function_name);
-    svg.push_str(&format!(
-        "  <rect x=\"{}\" y=\"{}\" width=\"240\" height=\"30\" rx=\"5\" class=\"current\" />\n  \
-  <text x=\"{}\" y=\"{}\" class=\"text\">{}</text>\n",
-        center_x - 120,
-        center_y,
-        center_x,
-        center_y + 20,
-        label
-    ));
+    let href = options.links.then(|| node_href(type_name, relationships, current_file)).flatten();
+    let type_class = if type_active { "type-node" } else { "type-node-inactive" };
+    let type_node =
+        doc.add_linked_rect(type_x as f64, type_y as f64, 300.0, 70.0, 5.0, type_class, type_name, href.as_deref());
+    if options.tooltips {
+        let all_methods: Vec<String> = inherent_impl
+            .into_iter()
+            .chain(trait_impls.iter().map(|(_, info)| *info))
+            .map(|info| method_tooltip_cfg(info, active_cfg))
+            .collect();
+        doc.set_title(type_node, all_methods.join("\n"));
+    }
+    doc.add_linked_text((type_x + 150) as f64, (type_y + 30) as f64, &type_label, "text", href.as_deref());
+    doc.add_text((type_x + 150) as f64, (type_y + 52) as f64, "struct", "method-text");
 
-    // Draw callee nodes
-    for (i, callee) in callees.iter().enumerate() {
-        let y = 50 + i * 40;
-        let label = callee.split("::").last().unwrap_or(callee);
-        svg.push_str(&format!(
-            "  <rect x=\"{}\" y=\"{}\" width=\"260\" height=\"30\" rx=\"5\" class=\"node\" />\n  \
-  <text x=\"{}\" y=\"{}\" class=\"text\">{}</text>\n",
-            width - 280,
-            y,
-            width - 150,
-            y + 20,
-            label
-        ));
+    if !type_active {
+        if let Some(predicate) = inherent_impl.and_then(|info| info.cfg.as_deref()) {
+            doc.add_text((type_x + 150) as f64, (type_y + 85) as f64, &format!("cfg({})", predicate), "cfg-label-text");
+        }
     }
 
-    svg.push_str("</svg>");
+    Some(doc.to_svg())
+}
+
+/// Same trait-implementation/supertrait data as [`generate_type_inheritance_graph`],
+/// serialized as Graphviz DOT. Skips the layered layout math entirely — DOT does its
+/// own node placement — so this only needs the node/edge declarations themselves.
+pub fn generate_type_inheritance_graph_dot(
+    type_name: &str,
+    relationships: &CodeRelationships,
+    active_cfg: &HashSet<String>,
+) -> Option<String> {
+    let trait_impls: Vec<(&String, &InheritanceInfo)> = relationships
+        .inheritance
+        .iter()
+        .filter(|(_, info)| {
+            info.type_name == type_name
+                && info.trait_name.is_some()
+                && cfg_predicate_satisfied(&info.cfg, active_cfg)
+        })
+        .collect();
+
+    let inherent_impl = relationships
+        .inheritance
+        .get(type_name)
+        .filter(|info| info.trait_name.is_none() && cfg_predicate_satisfied(&info.cfg, active_cfg));
+
+    if trait_impls.is_empty() && inherent_impl.is_none() {
+        return None;
+    }
+
+    let simple_type = disambiguated_label(type_name, relationships);
+    let mut doc = Document::new(1, 1);
+    doc.add_rect(0.0, 0.0, 1.0, 1.0, 0.0, "type-node");
+    doc.add_text(0.0, 0.0, &simple_type, "text");
+
+    if trait_impls.is_empty() {
+        return Some(doc.to_dot());
+    }
+
+    let supertraits: HashSet<String> = trait_impls
+        .iter()
+        .flat_map(|(_, info)| info.parent_traits.iter().cloned())
+        .collect();
+
+    for (_, info) in &trait_impls {
+        let trait_name = info.trait_name.as_ref().unwrap();
+        let trait_label = if info.is_blanket && !info.bounds.is_empty() {
+            format!(
+                "trait {} (blanket, where {})",
+                trait_name.split("::").last().unwrap_or(trait_name),
+                info.bounds.join(", ")
+            )
+        } else {
+            format!("trait {}", trait_name.split("::").last().unwrap_or(trait_name))
+        };
+        doc.add_rect(0.0, 0.0, 1.0, 1.0, 0.0, "trait-node");
+        doc.add_text(0.0, 0.0, &trait_label, "text");
+
+        for parent_trait in &info.parent_traits {
+            let parent_label = format!("trait {}", parent_trait.split("::").last().unwrap_or(parent_trait));
+            doc.add_edge(0.0, 0.0, 0.0, 0.0, "super-edge", &trait_label, &parent_label);
+        }
+
+        if !supertraits.contains(trait_name) {
+            let edge_class = if info.is_blanket { "blanket-edge" } else { "impl-edge" };
+            doc.add_edge(0.0, 0.0, 0.0, 0.0, edge_class, &trait_label, &simple_type);
+        }
+    }
+
+    Some(doc.to_dot())
+}
+
+/// Generate a simple SVG call graph for a specific function, restricted to the
+/// function itself plus the callers/callees whose `#[cfg(...)]` predicate (if any)
+/// is satisfied by `active_cfg` — an empty set renders the no-flags configuration
+/// (unconditional code plus items whose predicate holds with no flags, e.g.
+/// `not(test)`), or populate it (e.g. with `"test"`) to render a specific configuration.
+pub fn generate_function_call_graph(
+    function_name: &str,
+    relationships: &CodeRelationships,
+    current_file: &Path,
+    active_cfg: &HashSet<String>,
+) -> Option<String> {
+    build_function_call_document(function_name, relationships, Some(current_file), active_cfg)
+        .map(|doc| doc.to_svg())
+}
+
+/// Same layout data as [`generate_function_call_graph`], serialized as Graphviz DOT
+/// instead of SVG — lets callers pipe a function's immediate call neighborhood
+/// through existing DOT tooling.
+pub fn generate_function_call_graph_dot(
+    function_name: &str,
+    relationships: &CodeRelationships,
+    active_cfg: &HashSet<String>,
+) -> Option<String> {
+    build_function_call_document(function_name, relationships, None, active_cfg).map(|doc| doc.to_dot())
+}
+
+/// Whether `name` should appear in a cfg-filtered graph: unconditionally true for
+/// names with no [`FunctionMetadata`] (external/unresolved callees, which carry no
+/// cfg information), otherwise gated on that function's own `cfg` predicate.
+fn is_cfg_active(name: &str, relationships: &CodeRelationships, active_cfg: &HashSet<String>) -> bool {
+    relationships
+        .functions
+        .get(name)
+        .is_none_or(|metadata| cfg_predicate_satisfied(&metadata.cfg, active_cfg))
+}
+
+fn build_function_call_document(
+    function_name: &str,
+    relationships: &CodeRelationships,
+    current_file: Option<&Path>,
+    active_cfg: &HashSet<String>,
+) -> Option<Document> {
+    // Check if function exists and is active under `active_cfg`
+    if !is_cfg_active(function_name, relationships, active_cfg) {
+        return None;
+    }
+    if !relationships.functions.contains_key(function_name) {
+        return None;
+    }
+
+    let callees = relationships
+        .call_graph
+        .get(function_name)
+        .map(|set| {
+            set.iter()
+                .filter(|(callee, _)| is_cfg_active(callee, relationships, active_cfg))
+                .cloned()
+                .collect::<Vec<_>>()
+        })
+        .unwrap_or_default();
+
+    let callers = relationships
+        .usage_graph
+        .get(function_name)
+        .map(|set| {
+            set.iter()
+                .filter(|(caller, _)| is_cfg_active(caller, relationships, active_cfg))
+                .cloned()
+                .collect::<Vec<_>>()
+        })
+        .unwrap_or_default();
+
+    if callees.is_empty() && callers.is_empty() {
+        return None;
+    }
+
+    // Simple vertical layout
+    let width = 800;
+    let height = 200 + (callees.len().max(callers.len()) * 40);
+    let center_x = width / 2;
+    let center_y = height / 2;
+    let current_label = disambiguated_label(function_name, relationships);
+
+    let mut doc = Document::new(width as u32, height as u32);
+    doc.set_style(
+        "    .node { fill: rgb(76, 175, 80); stroke: rgb(46, 125, 50); stroke-width: 2; }\n    \
+    .current { fill: rgb(33, 150, 243); stroke: rgb(21, 101, 192); stroke-width: 3; }\n    \
+    .caller { fill: rgb(255, 193, 7); stroke: rgb(245, 124, 0); stroke-width: 2; }\n    \
+    .edge { stroke: rgb(102, 102, 102); stroke-width: 2; marker-end: url(#arrowhead); }\n    \
+    .caller-edge { stroke: rgb(245, 124, 0); stroke-width: 2; marker-end: url(#arrowhead); }\n    \
+    .await-edge { stroke: rgb(156, 39, 176); stroke-width: 2; stroke-dasharray: 4 2; marker-end: url(#arrowhead); }\n    \
+    .text { fill: white; font-family: monospace; font-size: 12px; text-anchor: middle; }"
+            .to_string(),
+    );
+    doc.set_defs(
+        "    <marker id=\"arrowhead\" markerWidth=\"10\" markerHeight=\"10\" refX=\"9\" refY=\"3\" orient=\"auto\">\n      \
+      <polygon points=\"0 0, 10 3, 0 6\" fill=\"rgb(102, 102, 102)\" />\n    \
+    </marker>"
+            .to_string(),
+    );
+
+    // Draw edges from callers to current function
+    for (i, (caller, awaited)) in callers.iter().enumerate() {
+        let y = 50 + i * 40;
+        let class = if *awaited { "await-edge" } else { "caller-edge" };
+        doc.add_edge(
+            150.0,
+            (y + 15) as f64,
+            (center_x - 120) as f64,
+            (center_y + 15) as f64,
+            class,
+            &disambiguated_label(caller, relationships),
+            &current_label,
+        );
+    }
+
+    // Draw edges from current function to callees
+    for (i, (callee, awaited)) in callees.iter().enumerate() {
+        let y = 50 + i * 40;
+        let class = if *awaited { "await-edge" } else { "edge" };
+        doc.add_edge(
+            (center_x + 120) as f64,
+            (center_y + 15) as f64,
+            (width - 150) as f64,
+            (y + 15) as f64,
+            class,
+            &current_label,
+            &disambiguated_label(callee, relationships),
+        );
+    }
+
+    // Draw caller nodes
+    for (i, (caller, _)) in callers.iter().enumerate() {
+        let y = 50 + i * 40;
+        let label = disambiguated_label(caller, relationships);
+        let href = current_file.and_then(|file| node_href(caller, relationships, file));
+        doc.add_linked_rect(20.0, y as f64, 260.0, 30.0, 5.0, "caller", caller, href.as_deref());
+        doc.add_text(150.0, (y + 20) as f64, &label, "text");
+    }
+
+    // Draw current function node
+    doc.add_rect((center_x - 120) as f64, center_y as f64, 240.0, 30.0, 5.0, "current");
+    doc.add_text(center_x as f64, (center_y + 20) as f64, &current_label, "text");
+
+    // Draw callee nodes
+    for (i, (callee, _)) in callees.iter().enumerate() {
+        let y = 50 + i * 40;
+        let label = disambiguated_label(callee, relationships);
+        let href = current_file.and_then(|file| node_href(callee, relationships, file));
+        doc.add_linked_rect(
+            (width - 280) as f64,
+            y as f64,
+            260.0,
+            30.0,
+            5.0,
+            "node",
+            callee,
+            href.as_deref(),
+        );
+        doc.add_text((width - 150) as f64, (y + 20) as f64, &label, "text");
+    }
+
+    Some(doc)
+}
+
+/// The set of functions reachable from the crate's externally-visible roots — every
+/// `pub` function, `main` (a binary's entry point, which nothing in-crate ever calls
+/// itself), and every method of a trait impl (reachable via dynamic dispatch or from
+/// outside the crate even when the impl block itself isn't `pub`) — walked through
+/// `call_graph` with a worklist DFS, the same shape `rustc`'s own reachability pass
+/// takes over the MIR call graph.
+pub fn compute_reachable_functions(relationships: &CodeRelationships) -> HashSet<String> {
+    let mut worklist: Vec<String> = relationships
+        .functions
+        .iter()
+        .filter(|(name, metadata)| {
+            metadata.is_public || name.as_str() == "main" || (metadata.is_method && metadata.parent_trait.is_some())
+        })
+        .map(|(name, _)| name.clone())
+        .collect();
+
+    for info in relationships.inheritance.values() {
+        if info.trait_name.is_some() {
+            for method in &info.methods {
+                worklist.push(format!("{}::{}", info.type_name, method));
+            }
+        }
+    }
+
+    let mut visited = HashSet::new();
+    while let Some(current) = worklist.pop() {
+        if !visited.insert(current.clone()) {
+            continue;
+        }
+        if let Some(callees) = relationships.call_graph.get(&current) {
+            for (callee, _) in callees {
+                if !visited.contains(callee) {
+                    worklist.push(callee.clone());
+                }
+            }
+        }
+    }
+
+    visited
+}
+
+/// Functions in `functions` that [`compute_reachable_functions`] never reaches from a
+/// root — candidate dead code. Sorted for stable reporting.
+pub fn find_unreachable_functions(relationships: &CodeRelationships) -> Vec<String> {
+    let reachable = compute_reachable_functions(relationships);
+    let mut unreachable: Vec<String> = relationships
+        .functions
+        .keys()
+        .filter(|name| !reachable.contains(name.as_str()))
+        .cloned()
+        .collect();
+    unreachable.sort();
+    unreachable
+}
+
+/// Render every function in `relationships` as a node in a simple grid, with
+/// `call_graph` edges drawn between them and nodes [`find_unreachable_functions`]
+/// flags dimmed via the `unreachable` CSS class — the whole-program counterpart to
+/// the one-hop views `generate_function_call_graph` produces. Returns `None` when
+/// there are no functions (after cfg filtering) to draw.
+pub fn generate_reachability_graph(
+    relationships: &CodeRelationships,
+    active_cfg: &HashSet<String>,
+) -> Option<String> {
+    let unreachable: HashSet<String> = find_unreachable_functions(relationships).into_iter().collect();
+
+    let mut names: Vec<&String> = relationships
+        .functions
+        .keys()
+        .filter(|name| is_cfg_active(name, relationships, active_cfg))
+        .collect();
+    names.sort();
+
+    if names.is_empty() {
+        return None;
+    }
+
+    const COLUMNS: usize = 4;
+    const CELL_WIDTH: usize = 220;
+    const CELL_HEIGHT: usize = 60;
+    let rows = names.len().div_ceil(COLUMNS);
+    let width = COLUMNS * CELL_WIDTH;
+    let height = rows * CELL_HEIGHT + 20;
+
+    let mut doc = Document::new(width as u32, height as u32);
+    doc.set_style(
+        "    .node { fill: rgb(76, 175, 80); stroke: rgb(46, 125, 50); stroke-width: 2; }\n    \
+    .unreachable { fill: rgb(158, 158, 158); stroke: rgb(97, 97, 97); stroke-width: 2; opacity: 0.5; }\n    \
+    .edge { stroke: rgb(102, 102, 102); stroke-width: 1; marker-end: url(#arrowhead); }\n    \
+    .text { fill: white; font-family: monospace; font-size: 11px; text-anchor: middle; }"
+            .to_string(),
+    );
+    doc.set_defs(
+        "    <marker id=\"arrowhead\" markerWidth=\"10\" markerHeight=\"10\" refX=\"9\" refY=\"3\" orient=\"auto\">\n      \
+      <polygon points=\"0 0, 10 3, 0 6\" fill=\"rgb(102, 102, 102)\" />\n    \
+    </marker>"
+            .to_string(),
+    );
+
+    let mut centers: HashMap<&str, (f64, f64)> = HashMap::new();
+    for (i, name) in names.iter().copied().enumerate() {
+        let col = i % COLUMNS;
+        let row = i / COLUMNS;
+        let x = (col * CELL_WIDTH + 10) as f64;
+        let y = (row * CELL_HEIGHT + 10) as f64;
+        let w = (CELL_WIDTH - 20) as f64;
+        let h = (CELL_HEIGHT - 20) as f64;
+        let class = if unreachable.contains(name.as_str()) { "unreachable" } else { "node" };
+        doc.add_rect(x, y, w, h, 5.0, class);
+        doc.add_text(x + w / 2.0, y + h / 2.0 + 4.0, &disambiguated_label(name, relationships), "text");
+        centers.insert(name.as_str(), (x + w / 2.0, y + h / 2.0));
+    }
+
+    for (caller, callees) in &relationships.call_graph {
+        let Some(&(x1, y1)) = centers.get(caller.as_str()) else {
+            continue;
+        };
+        for (callee, _) in callees {
+            let Some(&(x2, y2)) = centers.get(callee.as_str()) else {
+                continue;
+            };
+            doc.add_edge(
+                x1,
+                y1,
+                x2,
+                y2,
+                "edge",
+                &disambiguated_label(caller, relationships),
+                &disambiguated_label(callee, relationships),
+            );
+        }
+    }
+
+    Some(doc.to_svg())
+}
+
+/// Accumulates basic blocks and edges while walking a function body, mirroring how
+/// rustc's `cfg::construct` keeps a "current block" that straight-line statements
+/// accumulate into, plus a stack of loop scopes for `break`/`continue`.
+struct CfgBuilder {
+    blocks: Vec<CfgBlock>,
+    current: usize,
+    exit: usize,
+    loop_stack: Vec<(usize, usize)>,
+}
+
+impl CfgBuilder {
+    fn new() -> Self {
+        let entry = CfgBlock {
+            id: 0,
+            statements: Vec::new(),
+            successors: Vec::new(),
+        };
+        let exit = CfgBlock {
+            id: 1,
+            statements: Vec::new(),
+            successors: Vec::new(),
+        };
+
+        CfgBuilder {
+            blocks: vec![entry, exit],
+            current: 0,
+            exit: 1,
+            loop_stack: Vec::new(),
+        }
+    }
+
+    fn new_block(&mut self) -> usize {
+        let id = self.blocks.len();
+        self.blocks.push(CfgBlock {
+            id,
+            statements: Vec::new(),
+            successors: Vec::new(),
+        });
+        id
+    }
+
+    fn add_edge(&mut self, from: usize, to: usize, label: Option<String>) {
+        self.blocks[from].successors.push(CfgEdge { target: to, label });
+    }
+
+    fn push_stmt(&mut self, text: String) {
+        self.blocks[self.current].statements.push(text);
+    }
+
+    fn build(self) -> ControlFlowGraph {
+        ControlFlowGraph {
+            blocks: self.blocks,
+            entry: 0,
+            exit: self.exit,
+        }
+    }
+}
+
+/// Build the intra-procedural control-flow graph for a function or method body.
+pub fn build_control_flow_graph(block: &syn::Block) -> ControlFlowGraph {
+    let mut builder = CfgBuilder::new();
+    walk_block(&mut builder, block);
+
+    if builder.current != builder.exit {
+        let current = builder.current;
+        let exit = builder.exit;
+        builder.add_edge(current, exit, None);
+    }
+
+    builder.build()
+}
+
+fn walk_block(builder: &mut CfgBuilder, block: &syn::Block) {
+    for stmt in &block.stmts {
+        walk_stmt(builder, stmt);
+    }
+}
+
+fn walk_stmt(builder: &mut CfgBuilder, stmt: &syn::Stmt) {
+    match stmt {
+        syn::Stmt::Local(local) => {
+            let name = pat_snippet(&local.pat);
+            builder.push_stmt(format!("let {}", name));
+            if let Some(init) = &local.init {
+                walk_expr(builder, &init.expr);
+            }
+        }
+        syn::Stmt::Expr(expr, _) => walk_expr(builder, expr),
+        syn::Stmt::Macro(stmt_macro) => {
+            let name = RelationshipVisitor::extract_path_name(&stmt_macro.mac.path);
+            builder.push_stmt(format!("{}!(...)", name));
+        }
+        syn::Stmt::Item(_) => builder.push_stmt("<item>".to_string()),
+    }
+}
+
+/// Visit `expr`, closing the current block and wiring successor/loop-scope edges for
+/// branching constructs (`if`, `match`, loops, `break`/`continue`/`return`), or just
+/// appending a short label for anything else.
+fn walk_expr(builder: &mut CfgBuilder, expr: &Expr) {
+    match expr {
+        Expr::If(expr_if) => {
+            builder.push_stmt(format!("if {}", expr_snippet(&expr_if.cond)));
+            let before = builder.current;
+            let merge = builder.new_block();
+
+            let then_start = builder.new_block();
+            builder.add_edge(before, then_start, Some("then".to_string()));
+            builder.current = then_start;
+            walk_block(builder, &expr_if.then_branch);
+            builder.add_edge(builder.current, merge, None);
+
+            match &expr_if.else_branch {
+                Some((_, else_expr)) => {
+                    let else_start = builder.new_block();
+                    builder.add_edge(before, else_start, Some("else".to_string()));
+                    builder.current = else_start;
+                    walk_expr(builder, else_expr);
+                    builder.add_edge(builder.current, merge, None);
+                }
+                None => {
+                    builder.add_edge(before, merge, Some("else".to_string()));
+                }
+            }
+
+            builder.current = merge;
+        }
+        Expr::Match(expr_match) => {
+            builder.push_stmt(format!("match {}", expr_snippet(&expr_match.expr)));
+            let before = builder.current;
+            let merge = builder.new_block();
+
+            for arm in &expr_match.arms {
+                let arm_start = builder.new_block();
+                builder.add_edge(before, arm_start, Some(pat_snippet(&arm.pat)));
+                builder.current = arm_start;
+                walk_expr(builder, &arm.body);
+                builder.add_edge(builder.current, merge, None);
+            }
+
+            builder.current = merge;
+        }
+        Expr::While(expr_while) => {
+            let header = builder.new_block();
+            builder.add_edge(builder.current, header, None);
+            builder.current = header;
+            builder.push_stmt(format!("while {}", expr_snippet(&expr_while.cond)));
+
+            let after = builder.new_block();
+            builder.loop_stack.push((header, after));
+
+            let body_start = builder.new_block();
+            builder.add_edge(header, body_start, Some("loop".to_string()));
+            builder.current = body_start;
+            walk_block(builder, &expr_while.body);
+            builder.add_edge(builder.current, header, None);
+
+            builder.add_edge(header, after, Some("exit".to_string()));
+            builder.loop_stack.pop();
+            builder.current = after;
+        }
+        Expr::ForLoop(expr_for) => {
+            let header = builder.new_block();
+            builder.add_edge(builder.current, header, None);
+            builder.current = header;
+            builder.push_stmt(format!("for in {}", expr_snippet(&expr_for.expr)));
+
+            let after = builder.new_block();
+            builder.loop_stack.push((header, after));
+
+            let body_start = builder.new_block();
+            builder.add_edge(header, body_start, Some("loop".to_string()));
+            builder.current = body_start;
+            walk_block(builder, &expr_for.body);
+            builder.add_edge(builder.current, header, None);
+
+            builder.add_edge(header, after, Some("exit".to_string()));
+            builder.loop_stack.pop();
+            builder.current = after;
+        }
+        Expr::Loop(expr_loop) => {
+            let header = builder.new_block();
+            builder.add_edge(builder.current, header, None);
+            builder.current = header;
+            builder.push_stmt("loop".to_string());
+
+            let after = builder.new_block();
+            builder.loop_stack.push((header, after));
+
+            let body_start = builder.new_block();
+            builder.add_edge(header, body_start, Some("loop".to_string()));
+            builder.current = body_start;
+            walk_block(builder, &expr_loop.body);
+            builder.add_edge(builder.current, header, None);
+
+            builder.loop_stack.pop();
+            builder.current = after;
+        }
+        Expr::Continue(_) => {
+            if let Some((header, _)) = builder.loop_stack.last().copied() {
+                builder.add_edge(builder.current, header, Some("continue".to_string()));
+            }
+            builder.current = builder.new_block();
+        }
+        Expr::Break(_) => {
+            if let Some((_, after)) = builder.loop_stack.last().copied() {
+                builder.add_edge(builder.current, after, Some("break".to_string()));
+            }
+            builder.current = builder.new_block();
+        }
+        Expr::Return(_) => {
+            let exit = builder.exit;
+            builder.add_edge(builder.current, exit, Some("return".to_string()));
+            builder.current = builder.new_block();
+        }
+        Expr::Block(expr_block) => walk_block(builder, &expr_block.block),
+        other => builder.push_stmt(expr_snippet(other)),
+    }
+}
+
+/// A short, best-effort textual label for an expression — good enough to read in a
+/// diagram, not a faithful reprint of the original source.
+fn expr_snippet(expr: &Expr) -> String {
+    match expr {
+        Expr::Call(ExprCall { func, .. }) => {
+            if let Expr::Path(expr_path) = &**func {
+                format!("{}(...)", RelationshipVisitor::extract_path_name(&expr_path.path))
+            } else {
+                "call(...)".to_string()
+            }
+        }
+        Expr::MethodCall(ExprMethodCall { method, .. }) => format!("{}(...)", method),
+        Expr::Binary(_) => "<binary expr>".to_string(),
+        Expr::Macro(expr_macro) => format!(
+            "{}!(...)",
+            RelationshipVisitor::extract_path_name(&expr_macro.mac.path)
+        ),
+        Expr::Assign(_) => "<assign>".to_string(),
+        Expr::Path(expr_path) => RelationshipVisitor::extract_path_name(&expr_path.path),
+        Expr::Lit(expr_lit) => lit_snippet(&expr_lit.lit),
+        _ => "<expr>".to_string(),
+    }
+}
+
+fn lit_snippet(lit: &syn::Lit) -> String {
+    match lit {
+        syn::Lit::Str(s) => format!("{:?}", s.value()),
+        syn::Lit::Int(i) => i.base10_digits().to_string(),
+        syn::Lit::Float(f) => f.base10_digits().to_string(),
+        syn::Lit::Bool(b) => b.value.to_string(),
+        _ => "<lit>".to_string(),
+    }
+}
+
+fn pat_snippet(pat: &syn::Pat) -> String {
+    match pat {
+        syn::Pat::Ident(pat_ident) => pat_ident.ident.to_string(),
+        syn::Pat::Wild(_) => "_".to_string(),
+        syn::Pat::TupleStruct(pat_tuple_struct) => {
+            RelationshipVisitor::extract_path_name(&pat_tuple_struct.path)
+        }
+        syn::Pat::Path(pat_path) => RelationshipVisitor::extract_path_name(&pat_path.path),
+        _ => "<pattern>".to_string(),
+    }
+}
+
+/// Render a function's control-flow graph as a simple top-to-bottom stack of basic
+/// blocks, with back-edges (loop bodies, `continue`) drawn the same way as forward
+/// ones — this isn't a real graph-layout algorithm, just consistent ordering by
+/// block id, which is how the rest of this module's SVGs work too.
+pub fn generate_function_cfg_graph(
+    function_name: &str,
+    relationships: &CodeRelationships,
+    _current_file: &Path,
+) -> Option<String> {
+    let cfg = relationships.cfgs.get(function_name)?;
+
+    let width: usize = 700;
+    let block_height: usize = 50;
+    let gap: usize = 40;
+    let height = 40 + cfg.blocks.len() * (block_height + gap);
+
+    let block_y = |id: usize| 20 + id * (block_height + gap);
+
+    let mut svg = format!(
+        "<svg width=\"{}\" height=\"{}\" xmlns=\"http://www.w3.org/2000/svg\">\n  \
+  <style>\n    \
+    .cfg-block {{ fill: rgb(255, 255, 255); stroke: rgb(69, 90, 100); stroke-width: 2; }}\n    \
+    .cfg-entry {{ fill: rgb(33, 150, 243); stroke: rgb(21, 101, 192); stroke-width: 2; }}\n    \
+    .cfg-exit {{ fill: rgb(244, 67, 54); stroke: rgb(198, 40, 40); stroke-width: 2; }}\n    \
+    .cfg-edge {{ stroke: rgb(102, 102, 102); stroke-width: 2; fill: none; marker-end: url(#cfg-arrowhead); }}\n    \
+    .cfg-text {{ fill: rgb(33, 33, 33); font-family: monospace; font-size: 11px; }}\n    \
+    .cfg-edge-label {{ fill: rgb(117, 117, 117); font-family: monospace; font-size: 10px; }}\n  \
+  </style>\n  \
+  <defs>\n    \
+    <marker id=\"cfg-arrowhead\" markerWidth=\"10\" markerHeight=\"10\" refX=\"9\" refY=\"3\" orient=\"auto\">\n      \
+      <polygon points=\"0 0, 10 3, 0 6\" fill=\"rgb(102, 102, 102)\" />\n    \
+    </marker>\n  \
+  </defs>\n",
+        width, height
+    );
+
+    for block in &cfg.blocks {
+        for edge in &block.successors {
+            let (y1, y2) = if edge.target >= block.id {
+                (block_y(block.id) + block_height, block_y(edge.target))
+            } else {
+                (block_y(block.id), block_y(edge.target) + block_height)
+            };
+            svg.push_str(&format!(
+                "  <path d=\"M {} {} L {} {}\" class=\"cfg-edge\" />\n",
+                width / 2,
+                y1,
+                width / 2,
+                y2
+            ));
+            if let Some(label) = &edge.label {
+                svg.push_str(&format!(
+                    "  <text x=\"{}\" y=\"{}\" class=\"cfg-edge-label\">{}</text>\n",
+                    width / 2 + 8,
+                    (y1 + y2) / 2,
+                    label
+                ));
+            }
+        }
+    }
+
+    for block in &cfg.blocks {
+        let y = block_y(block.id);
+        let class = if block.id == cfg.entry {
+            "cfg-entry"
+        } else if block.id == cfg.exit {
+            "cfg-exit"
+        } else {
+            "cfg-block"
+        };
+
+        svg.push_str(&format!(
+            "  <rect x=\"40\" y=\"{}\" width=\"{}\" height=\"{}\" rx=\"5\" class=\"{}\" />\n",
+            y,
+            width - 80,
+            block_height,
+            class
+        ));
+
+        let label = if block.id == cfg.entry {
+            "entry".to_string()
+        } else if block.id == cfg.exit {
+            "exit".to_string()
+        } else if block.statements.is_empty() {
+            format!("bb{}", block.id)
+        } else {
+            block.statements.join("; ")
+        };
+
+        svg.push_str(&format!(
+            "  <text x=\"50\" y=\"{}\" class=\"cfg-text\">{}</text>\n",
+            y + block_height / 2 + 4,
+            label
+        ));
+    }
+
+    svg.push_str("</svg>");
 
     Some(svg)
 }
@@ -746,31 +2788,65 @@ mod tests {
         let mut call_graph = HashMap::new();
         let mut inheritance = HashMap::new();
         let mut functions = HashMap::new();
+        let mut cfgs = HashMap::new();
+        let mut imports = HashMap::new();
+        let mut import_globs = Vec::new();
 
         let mut visitor = RelationshipVisitor::new(
             &mut call_graph,
             &mut inheritance,
             &mut functions,
+            &mut cfgs,
+            &mut imports,
+            &mut import_globs,
             PathBuf::from("test.rs"),
         );
         visitor.visit_file(&ast);
 
+        resolve_call_graph(&mut call_graph, &functions, &imports);
+        let mut inheritance = resolve_inheritance_aliases(inheritance, &imports);
+
         // Build usage graph
         let mut usage_graph = HashMap::new();
         for (caller, callees) in &call_graph {
-            for callee in callees {
+            for (callee, awaited) in callees {
                 usage_graph
                     .entry(callee.clone())
                     .or_insert_with(HashSet::new)
-                    .insert(caller.clone());
+                    .insert((caller.clone(), *awaited));
+            }
+        }
+
+        // Populate parent_traits for trait implementations, mirroring extract_relationships
+        let trait_definitions: HashMap<String, Vec<String>> = inheritance
+            .iter()
+            .filter(|(key, info)| {
+                key.starts_with("__trait_def::") && info.type_name == "__trait_definition__"
+            })
+            .map(|(key, info)| {
+                let trait_name = key.strip_prefix("__trait_def::").unwrap_or("");
+                (trait_name.to_string(), info.parent_traits.clone())
+            })
+            .collect();
+
+        for (_, info) in inheritance.iter_mut() {
+            if let Some(ref trait_name) = info.trait_name {
+                if let Some(supertraits) = trait_definitions.get(trait_name) {
+                    info.parent_traits = supertraits.clone();
+                }
             }
         }
 
+        inheritance.retain(|key, _| !key.starts_with("__trait_def::"));
+
         CodeRelationships {
             call_graph,
             usage_graph,
             inheritance,
+            trait_supertraits: trait_definitions,
             functions,
+            verus_contracts: crate::verus_contracts::extract_verus_contracts(code),
+            cfgs,
         }
     }
 
@@ -786,7 +2862,7 @@ mod tests {
         let rels = parse_and_extract(code);
 
         assert!(rels.call_graph.contains_key("foo"));
-        assert!(rels.call_graph["foo"].contains("bar"));
+        assert!(rels.call_graph["foo"].iter().any(|(n, _)| n == "bar"));
         assert_eq!(rels.functions.len(), 2);
     }
 
@@ -801,974 +2877,2171 @@ mod tests {
 
         let rels = parse_and_extract(code);
 
-        assert!(rels.call_graph.contains_key("foo"));
-        assert!(rels.call_graph["foo"].contains("len"));
-        assert!(rels.call_graph["foo"].contains("String::new"));
+        assert!(rels.call_graph.contains_key("foo"));
+        // `s` is a known `String`, so the method call resolves to `String::len`
+        // rather than a bare, ambiguous `len` node.
+        assert!(rels.call_graph["foo"].iter().any(|(n, _)| n == "String::len"));
+        assert!(rels.call_graph["foo"].iter().any(|(n, _)| n == "String::new"));
+    }
+
+    #[test]
+    fn test_usage_graph() {
+        let code = r#"
+            fn caller1() { target(); }
+            fn caller2() { target(); }
+            fn target() {}
+        "#;
+
+        let rels = parse_and_extract(code);
+
+        assert!(rels.usage_graph.contains_key("target"));
+        assert!(rels.usage_graph["target"].iter().any(|(n, _)| n == "caller1"));
+        assert!(rels.usage_graph["target"].iter().any(|(n, _)| n == "caller2"));
+        assert_eq!(rels.usage_graph["target"].len(), 2);
+    }
+
+    #[test]
+    fn test_no_calls() {
+        let code = r#"
+            fn standalone() {
+                let x = 42;
+            }
+        "#;
+
+        let rels = parse_and_extract(code);
+
+        assert!(!rels.call_graph.contains_key("standalone"));
+        assert_eq!(rels.functions.len(), 1);
+    }
+
+    #[test]
+    fn test_impl_methods() {
+        let code = r#"
+            struct MyStruct;
+            impl MyStruct {
+                fn method(&self) {
+                    helper();
+                }
+            }
+            fn helper() {}
+        "#;
+
+        let rels = parse_and_extract(code);
+
+        assert!(rels.functions.contains_key("MyStruct::method"));
+        assert!(rels.call_graph.contains_key("MyStruct::method"));
+        assert!(rels.call_graph["MyStruct::method"].iter().any(|(n, _)| n == "helper"));
+    }
+
+    #[test]
+    fn test_public_private_functions() {
+        let code = r#"
+            pub fn public_fn() {}
+            fn private_fn() {}
+        "#;
+
+        let rels = parse_and_extract(code);
+
+        assert!(rels.functions["public_fn"].is_public);
+        assert!(!rels.functions["private_fn"].is_public);
+    }
+
+    #[test]
+    fn test_function_metadata() {
+        let code = r#"
+            pub fn my_function() {}
+        "#;
+
+        let rels = parse_and_extract(code);
+
+        let metadata = &rels.functions["my_function"];
+        assert_eq!(metadata.name, "my_function");
+        assert_eq!(metadata.fully_qualified_name, "my_function");
+        assert!(!metadata.is_method);
+        assert!(metadata.is_public);
+        assert_eq!(metadata.file_path, PathBuf::from("test.rs"));
+    }
+
+    #[test]
+    fn test_generate_call_graph_with_calls() {
+        let code = r#"
+            fn foo() { bar(); }
+            fn bar() {}
+        "#;
+
+        let rels = parse_and_extract(code);
+        let svg = generate_function_call_graph("foo", &rels, &PathBuf::from("test.rs"), &HashSet::new());
+
+        assert!(svg.is_some());
+        let svg_content = svg.unwrap();
+        assert!(svg_content.contains("<svg"));
+        assert!(svg_content.contains("foo"));
+        assert!(svg_content.contains("bar"));
+    }
+
+    #[test]
+    fn test_generate_call_graph_no_calls() {
+        let code = r#"
+            fn standalone() {
+                let x = 42;
+            }
+        "#;
+
+        let rels = parse_and_extract(code);
+        let svg = generate_function_call_graph("standalone", &rels, &PathBuf::from("test.rs"), &HashSet::new());
+
+        assert!(svg.is_none());
+    }
+
+    #[test]
+    fn test_generate_call_graph_nonexistent_function() {
+        let code = r#"
+            fn foo() {}
+        "#;
+
+        let rels = parse_and_extract(code);
+        let svg = generate_function_call_graph("nonexistent", &rels, &PathBuf::from("test.rs"), &HashSet::new());
+
+        assert!(svg.is_none());
+    }
+
+    #[test]
+    fn test_call_graph_links_known_callee() {
+        let code = r#"
+            fn foo() { bar(); }
+            fn bar() {}
+        "#;
+
+        let rels = parse_and_extract(code);
+        let svg = generate_function_call_graph("foo", &rels, &PathBuf::from("fn.foo.html"), &HashSet::new()).unwrap();
+
+        assert!(svg.contains("<a href=\"fn.bar.html\">"));
+    }
+
+    #[test]
+    fn test_call_graph_does_not_link_external_callee() {
+        let code = r#"
+            fn foo() { std::mem::drop(1); }
+        "#;
+
+        let rels = parse_and_extract(code);
+        let svg = generate_function_call_graph("foo", &rels, &PathBuf::from("fn.foo.html"), &HashSet::new()).unwrap();
+
+        assert!(!svg.contains("<a href"));
+    }
+
+    #[test]
+    fn test_resolve_doc_href_for_function() {
+        let code = "pub fn my_function() {}";
+        let rels = parse_and_extract(code);
+
+        assert_eq!(
+            resolve_doc_href("my_function", &rels),
+            Some("fn.my_function.html".to_string())
+        );
+    }
+
+    #[test]
+    fn test_resolve_doc_href_for_method() {
+        let code = r#"
+            struct Foo;
+            impl Foo {
+                fn method(&self) {}
+            }
+        "#;
+        let rels = parse_and_extract(code);
+
+        assert_eq!(
+            resolve_doc_href("Foo::method", &rels),
+            Some("struct.Foo.html#method.method".to_string())
+        );
+    }
+
+    #[test]
+    fn test_resolve_doc_href_unknown_name() {
+        let code = "fn foo() {}";
+        let rels = parse_and_extract(code);
+
+        assert_eq!(resolve_doc_href("std::mem::drop", &rels), None);
+    }
+
+    #[test]
+    fn test_multiple_calls() {
+        let code = r#"
+            fn foo() {
+                bar();
+                baz();
+                qux();
+            }
+            fn bar() {}
+            fn baz() {}
+            fn qux() {}
+        "#;
+
+        let rels = parse_and_extract(code);
+
+        assert_eq!(rels.call_graph["foo"].len(), 3);
+        assert!(rels.call_graph["foo"].iter().any(|(n, _)| n == "bar"));
+        assert!(rels.call_graph["foo"].iter().any(|(n, _)| n == "baz"));
+        assert!(rels.call_graph["foo"].iter().any(|(n, _)| n == "qux"));
+    }
+
+    #[test]
+    fn test_call_chain() {
+        let code = r#"
+            fn a() { b(); }
+            fn b() { c(); }
+            fn c() {}
+        "#;
+
+        let rels = parse_and_extract(code);
+
+        assert!(rels.call_graph["a"].iter().any(|(n, _)| n == "b"));
+        assert!(rels.call_graph["b"].iter().any(|(n, _)| n == "c"));
+        assert!(rels.usage_graph["b"].iter().any(|(n, _)| n == "a"));
+        assert!(rels.usage_graph["c"].iter().any(|(n, _)| n == "b"));
+    }
+
+    #[test]
+    fn test_trait_impl() {
+        let code = r#"
+            trait MyTrait {
+                fn trait_method(&self);
+            }
+
+            struct MyStruct;
+
+            impl MyTrait for MyStruct {
+                fn trait_method(&self) {
+                    helper();
+                }
+            }
+
+            fn helper() {}
+        "#;
+
+        let rels = parse_and_extract(code);
+
+        assert!(rels.functions.contains_key("MyStruct::trait_method"));
+        assert!(rels.inheritance.contains_key("MyStruct::MyTrait"));
+    }
+
+    #[test]
+    fn test_nested_calls() {
+        let code = r#"
+            fn outer() {
+                inner(middle());
+            }
+            fn middle() {}
+            fn inner(x: ()) {}
+        "#;
+
+        let rels = parse_and_extract(code);
+
+        assert!(rels.call_graph["outer"].iter().any(|(n, _)| n == "middle"));
+        assert!(rels.call_graph["outer"].iter().any(|(n, _)| n == "inner"));
+        assert_eq!(rels.call_graph["outer"].len(), 2);
+    }
+
+    #[test]
+    fn test_self_recursion() {
+        let code = r#"
+            fn recursive(n: i32) {
+                if n > 0 {
+                    recursive(n - 1);
+                }
+            }
+        "#;
+
+        let rels = parse_and_extract(code);
+
+        assert!(rels.call_graph.contains_key("recursive"));
+        assert!(rels.call_graph["recursive"].iter().any(|(n, _)| n == "recursive"));
+        assert!(rels.usage_graph["recursive"].iter().any(|(n, _)| n == "recursive"));
+    }
+
+    #[test]
+    fn test_mutual_recursion() {
+        let code = r#"
+            fn foo(n: i32) {
+                if n > 0 { bar(n - 1); }
+            }
+            fn bar(n: i32) {
+                if n > 0 { foo(n - 1); }
+            }
+        "#;
+
+        let rels = parse_and_extract(code);
+
+        assert!(rels.call_graph["foo"].iter().any(|(n, _)| n == "bar"));
+        assert!(rels.call_graph["bar"].iter().any(|(n, _)| n == "foo"));
+    }
+
+    #[test]
+    fn test_qualified_path_call() {
+        let code = r#"
+            fn test() {
+                std::mem::drop(42);
+            }
+        "#;
+
+        let rels = parse_and_extract(code);
+
+        assert!(rels.call_graph.contains_key("test"));
+        assert!(rels.call_graph["test"].iter().any(|(n, _)| n == "std::mem::drop"));
+    }
+
+    #[test]
+    fn test_fully_qualified_trait_call() {
+        let code = r#"
+            trait Greet {
+                fn hello();
+            }
+            struct Foo;
+            impl Greet for Foo {
+                fn hello() {}
+            }
+            fn test() {
+                <Foo as Greet>::hello();
+            }
+        "#;
+
+        let rels = parse_and_extract(code);
+
+        assert!(rels.call_graph["test"].iter().any(|(n, _)| n == "Foo::hello"));
+        assert!(!rels.call_graph["test"].iter().any(|(n, _)| n == "Greet::hello"));
+    }
+
+    #[test]
+    fn test_method_on_type() {
+        let code = r#"
+            struct Foo;
+            impl Foo {
+                fn new() -> Self { Foo }
+                fn method(&self) {
+                    Self::new();
+                }
+            }
+        "#;
+
+        let rels = parse_and_extract(code);
+
+        assert!(rels.functions.contains_key("Foo::new"));
+        assert!(rels.functions.contains_key("Foo::method"));
+        assert!(rels.call_graph["Foo::method"].iter().any(|(n, _)| n == "Self::new"));
+    }
+
+    #[test]
+    fn test_closure_calls() {
+        let code = r#"
+            fn outer() {
+                let closure = || {
+                    inner();
+                };
+                closure();
+            }
+            fn inner() {}
+        "#;
+
+        let rels = parse_and_extract(code);
+
+        // Closures are captured within the outer function's scope
+        assert!(rels.call_graph.contains_key("outer"));
+        assert!(rels.call_graph["outer"].iter().any(|(n, _)| n == "inner"));
+    }
+
+    #[test]
+    fn test_generic_function() {
+        let code = r#"
+            fn generic<T>(x: T) {
+                helper();
+            }
+            fn helper() {}
+        "#;
+
+        let rels = parse_and_extract(code);
+
+        assert!(rels.functions.contains_key("generic"));
+        assert!(rels.call_graph["generic"].iter().any(|(n, _)| n == "helper"));
+    }
+
+    #[test]
+    fn test_async_function() {
+        let code = r#"
+            async fn async_fn() {
+                other().await;
+            }
+            async fn other() {}
+        "#;
+
+        let rels = parse_and_extract(code);
+
+        assert!(rels.functions["async_fn"].is_async);
+        assert!(rels.functions["other"].is_async);
+        assert!(rels.call_graph["async_fn"].contains(&("other".to_string(), true)));
+    }
+
+    #[test]
+    fn test_async_function_call_without_await_is_not_marked_awaited() {
+        let code = r#"
+            async fn async_fn() {
+                let _future = other();
+            }
+            async fn other() {}
+        "#;
+
+        let rels = parse_and_extract(code);
+
+        assert!(rels.call_graph["async_fn"].contains(&("other".to_string(), false)));
+    }
+
+    #[test]
+    fn test_const_function() {
+        let code = r#"
+            const fn const_fn() {
+                helper();
+            }
+            const fn helper() {}
+        "#;
+
+        let rels = parse_and_extract(code);
+
+        assert!(rels.functions.contains_key("const_fn"));
+        assert!(rels.call_graph["const_fn"].iter().any(|(n, _)| n == "helper"));
+    }
+
+    #[test]
+    fn test_multiple_impls_same_type() {
+        let code = r#"
+            struct Foo;
+
+            impl Foo {
+                fn method1(&self) {}
+            }
+
+            impl Foo {
+                fn method2(&self) {}
+            }
+        "#;
+
+        let rels = parse_and_extract(code);
+
+        assert!(rels.functions.contains_key("Foo::method1"));
+        assert!(rels.functions.contains_key("Foo::method2"));
+    }
+
+    #[test]
+    fn test_empty_function() {
+        let code = r#"
+            fn empty() {}
+        "#;
+
+        let rels = parse_and_extract(code);
+
+        assert_eq!(rels.functions.len(), 1);
+        assert!(!rels.call_graph.contains_key("empty"));
+        assert!(!rels.usage_graph.contains_key("empty"));
+    }
+
+    #[test]
+    fn test_call_graph_with_callers_and_callees() {
+        let code = r#"
+            fn caller1() { middle(); }
+            fn caller2() { middle(); }
+            fn middle() { callee(); }
+            fn callee() {}
+        "#;
+
+        let rels = parse_and_extract(code);
+        let svg = generate_function_call_graph("middle", &rels, &PathBuf::from("test.rs"), &HashSet::new());
+
+        assert!(svg.is_some());
+        let svg_content = svg.unwrap();
+
+        // Should contain both callers and callees
+        assert!(svg_content.contains("caller1") || svg_content.contains("caller2"));
+        assert!(svg_content.contains("callee"));
+        assert!(svg_content.contains("middle"));
+
+        // Should have caller-edge class for incoming edges
+        assert!(svg_content.contains("caller-edge"));
+
+        // Should have regular edge class for outgoing edges
+        assert!(svg_content.contains("class=\"edge\""));
+    }
+
+    #[test]
+    fn test_function_with_only_callers() {
+        let code = r#"
+            fn caller1() { target(); }
+            fn caller2() { target(); }
+            fn target() {}
+        "#;
+
+        let rels = parse_and_extract(code);
+        let svg = generate_function_call_graph("target", &rels, &PathBuf::from("test.rs"), &HashSet::new());
+
+        assert!(svg.is_some());
+        let svg_content = svg.unwrap();
+        assert!(svg_content.contains("caller"));
+        assert!(svg_content.contains("target"));
+    }
+
+    #[test]
+    fn test_function_with_only_callees() {
+        let code = r#"
+            fn caller() {
+                callee1();
+                callee2();
+            }
+            fn callee1() {}
+            fn callee2() {}
+        "#;
+
+        let rels = parse_and_extract(code);
+        let svg = generate_function_call_graph("caller", &rels, &PathBuf::from("test.rs"), &HashSet::new());
+
+        assert!(svg.is_some());
+        let svg_content = svg.unwrap();
+        assert!(svg_content.contains("callee"));
+        assert!(svg_content.contains("caller"));
+    }
+
+    #[test]
+    fn test_extract_path_name() {
+        let code = r#"
+            fn test() {
+                std::collections::HashMap::new();
+            }
+        "#;
+
+        let rels = parse_and_extract(code);
+
+        assert!(rels.call_graph.contains_key("test"));
+        assert!(rels.call_graph["test"].iter().any(|(n, _)| n == "std::collections::HashMap::new"));
+    }
+
+    #[test]
+    fn test_method_is_marked_as_method() {
+        let code = r#"
+            struct Foo;
+            impl Foo {
+                fn is_method(&self) {}
+            }
+            fn is_function() {}
+        "#;
+
+        let rels = parse_and_extract(code);
+
+        assert!(rels.functions["Foo::is_method"].is_method);
+        assert!(!rels.functions["is_function"].is_method);
+    }
+
+    #[test]
+    fn test_turbofish_syntax() {
+        let code = r#"
+            fn caller() {
+                helper::<i32>();
+            }
+            fn helper<T>() {}
+        "#;
+
+        let rels = parse_and_extract(code);
+
+        assert!(rels.call_graph["caller"].iter().any(|(n, _)| n == "helper"));
+    }
+
+    #[test]
+    fn test_match_with_calls() {
+        let code = r#"
+            fn test(x: Option<i32>) {
+                match x {
+                    Some(_) => handle_some(),
+                    None => handle_none(),
+                }
+            }
+            fn handle_some() {}
+            fn handle_none() {}
+        "#;
+
+        let rels = parse_and_extract(code);
+
+        assert!(rels.call_graph["test"].iter().any(|(n, _)| n == "handle_some"));
+        assert!(rels.call_graph["test"].iter().any(|(n, _)| n == "handle_none"));
+    }
+
+    #[test]
+    fn test_if_else_with_calls() {
+        let code = r#"
+            fn test(condition: bool) {
+                if condition {
+                    branch_true();
+                } else {
+                    branch_false();
+                }
+            }
+            fn branch_true() {}
+            fn branch_false() {}
+        "#;
+
+        let rels = parse_and_extract(code);
+
+        assert!(rels.call_graph["test"].iter().any(|(n, _)| n == "branch_true"));
+        assert!(rels.call_graph["test"].iter().any(|(n, _)| n == "branch_false"));
+    }
+
+    #[test]
+    fn test_loop_with_calls() {
+        let code = r#"
+            fn test() {
+                loop {
+                    if condition() {
+                        break;
+                    }
+                    action();
+                }
+            }
+            fn condition() -> bool { true }
+            fn action() {}
+        "#;
+
+        let rels = parse_and_extract(code);
+
+        assert!(rels.call_graph["test"].iter().any(|(n, _)| n == "condition"));
+        assert!(rels.call_graph["test"].iter().any(|(n, _)| n == "action"));
+    }
+
+    #[test]
+    fn test_chained_method_calls() {
+        let code = r#"
+            fn test() {
+                vec![1, 2, 3]
+                    .iter()
+                    .map()
+                    .collect();
+            }
+        "#;
+
+        let rels = parse_and_extract(code);
+
+        assert!(rels.call_graph.contains_key("test"));
+        assert!(rels.call_graph["test"].iter().any(|(n, _)| n == "iter"));
+        assert!(rels.call_graph["test"].iter().any(|(n, _)| n == "map"));
+        assert!(rels.call_graph["test"].iter().any(|(n, _)| n == "collect"));
+    }
+
+    #[test]
+    fn test_inheritance_graph_single_trait() {
+        let code = r#"
+            trait Greeter {
+                fn greet(&self);
+            }
+
+            struct FriendlyGreeter;
+
+            impl Greeter for FriendlyGreeter {
+                fn greet(&self) {}
+            }
+        "#;
+
+        let rels = parse_and_extract(code);
+        let svg = generate_type_inheritance_graph("FriendlyGreeter", &rels, &PathBuf::from("test.rs"), &HashSet::new(), GraphOptions::default());
+
+        assert!(svg.is_some());
+        let svg_content = svg.unwrap();
+        assert!(svg_content.contains("<svg"));
+        assert!(svg_content.contains("FriendlyGreeter"));
+        assert!(svg_content.contains("Greeter"));
+        assert!(svg_content.contains("trait-node"));
+        assert!(svg_content.contains("type-node"));
+    }
+
+    #[test]
+    fn test_inheritance_graph_links_to_type_page() {
+        let code = r#"
+            trait Greeter {
+                fn greet(&self);
+            }
+
+            struct FriendlyGreeter;
+
+            impl Greeter for FriendlyGreeter {
+                fn greet(&self) {}
+            }
+        "#;
+
+        let rels = parse_and_extract(code);
+        let svg = generate_type_inheritance_graph(
+            "FriendlyGreeter",
+            &rels,
+            &PathBuf::from("enum.SomethingElse.html"),
+            &HashSet::new(),
+            GraphOptions::default(),
+        )
+        .unwrap();
+
+        assert!(svg.contains("<a href=\"struct.FriendlyGreeter.html\">"));
+    }
+
+    #[test]
+    fn test_inheritance_graph_does_not_link_to_own_page() {
+        let code = r#"
+            struct PlainStruct;
+
+            impl PlainStruct {
+                fn method(&self) {}
+            }
+        "#;
+
+        let rels = parse_and_extract(code);
+        let svg = generate_type_inheritance_graph(
+            "PlainStruct",
+            &rels,
+            &PathBuf::from("struct.PlainStruct.html"),
+            &HashSet::new(),
+            GraphOptions::default(),
+        )
+        .unwrap();
+
+        assert!(!svg.contains("<a href"));
+    }
+
+    #[test]
+    fn test_inheritance_graph_multiple_traits() {
+        let code = r#"
+            trait Trait1 {
+                fn method1(&self);
+            }
+
+            trait Trait2 {
+                fn method2(&self);
+            }
+
+            struct MyType;
+
+            impl Trait1 for MyType {
+                fn method1(&self) {}
+            }
+
+            impl Trait2 for MyType {
+                fn method2(&self) {}
+            }
+        "#;
+
+        let rels = parse_and_extract(code);
+        let svg = generate_type_inheritance_graph("MyType", &rels, &PathBuf::from("test.rs"), &HashSet::new(), GraphOptions::default());
+
+        assert!(svg.is_some());
+        let svg_content = svg.unwrap();
+        assert!(svg_content.contains("MyType"));
+        assert!(svg_content.contains("Trait1"));
+        assert!(svg_content.contains("Trait2"));
+    }
+
+    #[test]
+    fn test_inheritance_graph_no_traits() {
+        let code = r#"
+            struct PlainStruct;
+
+            impl PlainStruct {
+                fn method(&self) {}
+            }
+        "#;
+
+        let rels = parse_and_extract(code);
+        let svg = generate_type_inheritance_graph("PlainStruct", &rels, &PathBuf::from("test.rs"), &HashSet::new(), GraphOptions::default());
+
+        // Should return Some because there's an inherent impl
+        assert!(svg.is_some());
+    }
+
+    #[test]
+    fn test_inheritance_graph_nonexistent_type() {
+        let code = r#"
+            struct Foo;
+        "#;
+
+        let rels = parse_and_extract(code);
+        let svg = generate_type_inheritance_graph("NonExistent", &rels, &PathBuf::from("test.rs"), &HashSet::new(), GraphOptions::default());
+
+        assert!(svg.is_none());
+    }
+
+    #[test]
+    fn test_inheritance_info_stored() {
+        let code = r#"
+            trait MyTrait {
+                fn trait_method(&self);
+            }
+
+            struct MyStruct;
+
+            impl MyTrait for MyStruct {
+                fn trait_method(&self) {}
+            }
+        "#;
+
+        let rels = parse_and_extract(code);
+
+        assert!(rels.inheritance.contains_key("MyStruct::MyTrait"));
+        let info = &rels.inheritance["MyStruct::MyTrait"];
+        assert_eq!(info.type_name, "MyStruct");
+        assert_eq!(info.trait_name, Some("MyTrait".to_string()));
+        assert_eq!(info.methods.len(), 1);
+        assert!(info.methods.contains(&"trait_method".to_string()));
+    }
+
+    #[test]
+    fn test_inheritance_graph_with_enum() {
+        let code = r#"
+            trait Handler {
+                fn handle(&self);
+            }
+
+            enum Event {
+                Click,
+                Hover,
+            }
+
+            impl Handler for Event {
+                fn handle(&self) {}
+            }
+        "#;
+
+        let rels = parse_and_extract(code);
+        let svg = generate_type_inheritance_graph("Event", &rels, &PathBuf::from("test.rs"), &HashSet::new(), GraphOptions::default());
+
+        assert!(svg.is_some());
+        let svg_content = svg.unwrap();
+        assert!(svg_content.contains("Event"));
+        assert!(svg_content.contains("Handler"));
+    }
+
+    #[test]
+    fn test_inheritance_graph_many_methods() {
+        let code = r#"
+            trait LargeTrait {
+                fn method1(&self);
+                fn method2(&self);
+                fn method3(&self);
+                fn method4(&self);
+                fn method5(&self);
+            }
+
+            struct MyType;
+
+            impl LargeTrait for MyType {
+                fn method1(&self) {}
+                fn method2(&self) {}
+                fn method3(&self) {}
+                fn method4(&self) {}
+                fn method5(&self) {}
+            }
+        "#;
+
+        let rels = parse_and_extract(code);
+        let svg = generate_type_inheritance_graph("MyType", &rels, &PathBuf::from("test.rs"), &HashSet::new(), GraphOptions::default());
+
+        assert!(svg.is_some());
+        let svg_content = svg.unwrap();
+        // Should show "5 methods" instead of listing all
+        assert!(svg_content.contains("5 methods"));
+    }
+
+    #[test]
+    fn test_inheritance_graph_few_methods_listed() {
+        let code = r#"
+            trait SmallTrait {
+                fn foo(&self);
+                fn bar(&self);
+            }
+
+            struct MyType;
+
+            impl SmallTrait for MyType {
+                fn foo(&self) {}
+                fn bar(&self) {}
+            }
+        "#;
+
+        let rels = parse_and_extract(code);
+        let svg = generate_type_inheritance_graph("MyType", &rels, &PathBuf::from("test.rs"), &HashSet::new(), GraphOptions::default());
+
+        assert!(svg.is_some());
+        let svg_content = svg.unwrap();
+        // Should list method names for 2 or fewer
+        assert!(svg_content.contains("foo"));
+        assert!(svg_content.contains("bar"));
+    }
+
+    #[test]
+    fn test_inheritance_graph_generic_trait() {
+        let code = r#"
+            trait Convert<T> {
+                fn convert(&self) -> T;
+            }
+
+            struct MyType;
+
+            impl Convert<String> for MyType {
+                fn convert(&self) -> String {
+                    String::new()
+                }
+            }
+        "#;
+
+        let rels = parse_and_extract(code);
+        let svg = generate_type_inheritance_graph("MyType", &rels, &PathBuf::from("test.rs"), &HashSet::new(), GraphOptions::default());
+
+        assert!(svg.is_some());
+        let svg_content = svg.unwrap();
+        assert!(svg_content.contains("MyType"));
+        assert!(svg_content.contains("Convert"));
+    }
+
+    #[test]
+    fn test_inheritance_graph_std_trait() {
+        let code = r#"
+            struct MyType;
+
+            impl std::fmt::Display for MyType {
+                fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+                    Ok(())
+                }
+            }
+        "#;
+
+        let rels = parse_and_extract(code);
+        let svg = generate_type_inheritance_graph("MyType", &rels, &PathBuf::from("test.rs"), &HashSet::new(), GraphOptions::default());
+
+        assert!(svg.is_some());
+        let svg_content = svg.unwrap();
+        assert!(svg_content.contains("MyType"));
+        // Should show simple name, not full path
+        assert!(svg_content.contains("Display"));
+    }
+
+    #[test]
+    fn test_inheritance_multiple_impls_same_trait() {
+        let code = r#"
+            struct TypeA;
+            struct TypeB;
+
+            trait Common {
+                fn common(&self);
+            }
+
+            impl Common for TypeA {
+                fn common(&self) {}
+            }
+
+            impl Common for TypeB {
+                fn common(&self) {}
+            }
+        "#;
+
+        let rels = parse_and_extract(code);
+
+        // Each type should have its own graph
+        let svg_a = generate_type_inheritance_graph("TypeA", &rels, &PathBuf::from("test.rs"), &HashSet::new(), GraphOptions::default());
+        let svg_b = generate_type_inheritance_graph("TypeB", &rels, &PathBuf::from("test.rs"), &HashSet::new(), GraphOptions::default());
+
+        assert!(svg_a.is_some());
+        assert!(svg_b.is_some());
+
+        let content_a = svg_a.unwrap();
+        let content_b = svg_b.unwrap();
+
+        assert!(content_a.contains("TypeA"));
+        assert!(content_a.contains("Common"));
+
+        assert!(content_b.contains("TypeB"));
+        assert!(content_b.contains("Common"));
+    }
+
+    #[test]
+    fn test_inheritance_trait_with_no_methods() {
+        let code = r#"
+            trait Marker {}
+
+            struct MyType;
+
+            impl Marker for MyType {}
+        "#;
+
+        let rels = parse_and_extract(code);
+        let svg = generate_type_inheritance_graph("MyType", &rels, &PathBuf::from("test.rs"), &HashSet::new(), GraphOptions::default());
+
+        assert!(svg.is_some());
+        let svg_content = svg.unwrap();
+        assert!(svg_content.contains("MyType"));
+        assert!(svg_content.contains("Marker"));
+    }
+
+    #[test]
+    fn test_inherent_impl_stored() {
+        let code = r#"
+            struct MyStruct;
+
+            impl MyStruct {
+                fn new() -> Self {
+                    MyStruct
+                }
+                fn method(&self) {}
+            }
+        "#;
+
+        let rels = parse_and_extract(code);
+
+        assert!(rels.inheritance.contains_key("MyStruct"));
+        let info = &rels.inheritance["MyStruct"];
+        assert_eq!(info.type_name, "MyStruct");
+        assert_eq!(info.trait_name, None);
+        assert_eq!(info.methods.len(), 2);
+        assert!(info.methods.contains(&"new".to_string()));
+        assert!(info.methods.contains(&"method".to_string()));
     }
 
     #[test]
-    fn test_usage_graph() {
+    fn test_cfg_gated_impl_records_cfg_on_inheritance_info() {
         let code = r#"
-            fn caller1() { target(); }
-            fn caller2() { target(); }
-            fn target() {}
+            struct MyStruct;
+
+            #[cfg(test)]
+            impl MyStruct {
+                fn foo1(&self) {}
+            }
         "#;
 
         let rels = parse_and_extract(code);
-
-        assert!(rels.usage_graph.contains_key("target"));
-        assert!(rels.usage_graph["target"].contains("caller1"));
-        assert!(rels.usage_graph["target"].contains("caller2"));
-        assert_eq!(rels.usage_graph["target"].len(), 2);
+        assert_eq!(rels.inheritance["MyStruct"].cfg.as_deref(), Some("test"));
     }
 
     #[test]
-    fn test_no_calls() {
+    fn test_cfg_gated_impls_do_not_collapse_methods_from_different_configurations() {
         let code = r#"
-            fn standalone() {
-                let x = 42;
+            struct MyStruct;
+
+            #[cfg(test)]
+            impl MyStruct {
+                fn foo1(&self) {}
+            }
+
+            #[cfg(not(test))]
+            impl MyStruct {
+                fn foo2(&self) {}
             }
         "#;
 
         let rels = parse_and_extract(code);
-
-        assert!(!rels.call_graph.contains_key("standalone"));
-        assert_eq!(rels.functions.len(), 1);
+        assert!(rels.functions.contains_key("MyStruct::foo1"));
+        assert!(rels.functions.contains_key("MyStruct::foo2"));
+        assert_eq!(rels.functions["MyStruct::foo1"].cfg.as_deref(), Some("test"));
+        assert_eq!(rels.functions["MyStruct::foo2"].cfg.as_deref(), Some("not (test)"));
     }
 
     #[test]
-    fn test_impl_methods() {
+    fn test_cfg_on_method_combines_with_enclosing_impl_cfg() {
         let code = r#"
             struct MyStruct;
+
+            #[cfg(unix)]
             impl MyStruct {
-                fn method(&self) {
-                    helper();
-                }
+                #[cfg(test)]
+                fn foo(&self) {}
             }
-            fn helper() {}
         "#;
 
         let rels = parse_and_extract(code);
-
-        assert!(rels.functions.contains_key("MyStruct::method"));
-        assert!(rels.call_graph.contains_key("MyStruct::method"));
-        assert!(rels.call_graph["MyStruct::method"].contains("helper"));
+        let cfg = rels.functions["MyStruct::foo"].cfg.as_deref().unwrap();
+        assert!(cfg.starts_with("all("));
+        assert!(cfg.contains("unix"));
+        assert!(cfg.contains("test"));
     }
 
     #[test]
-    fn test_public_private_functions() {
+    fn test_inheritance_info_records_per_method_cfg() {
         let code = r#"
-            pub fn public_fn() {}
-            fn private_fn() {}
+            struct MyStruct;
+
+            #[cfg(unix)]
+            impl MyStruct {
+                #[cfg(test)]
+                fn foo(&self) {}
+                fn bar(&self) {}
+            }
         "#;
 
         let rels = parse_and_extract(code);
+        let info = &rels.inheritance["MyStruct"];
 
-        assert!(rels.functions["public_fn"].is_public);
-        assert!(!rels.functions["private_fn"].is_public);
+        let foo_cfg = info.method_cfgs["foo"].as_deref().unwrap();
+        assert!(foo_cfg.starts_with("all("));
+        assert!(foo_cfg.contains("unix"));
+        assert!(foo_cfg.contains("test"));
+
+        assert_eq!(info.method_cfgs["bar"].as_deref(), Some("unix"));
     }
 
     #[test]
-    fn test_function_metadata() {
+    fn test_generate_type_inheritance_graph_cfg_greys_out_inactive_impl() {
         let code = r#"
-            pub fn my_function() {}
+            trait Greeting {
+                fn greet(&self);
+            }
+            struct MyType;
+
+            #[cfg(test)]
+            impl Greeting for MyType {
+                fn greet(&self) {}
+            }
         "#;
 
         let rels = parse_and_extract(code);
+        let active = HashSet::new();
+
+        // The plain variant hides the cfg-inactive impl entirely...
+        assert!(generate_type_inheritance_graph(
+            "MyType",
+            &rels,
+            &PathBuf::from("test.rs"),
+            &active,
+            GraphOptions::default()
+        )
+        .is_none());
+
+        // ...whereas the cfg-aware variant keeps it, greyed out, with its predicate
+        // shown as a label.
+        let svg = generate_type_inheritance_graph_cfg(
+            "MyType",
+            &rels,
+            &PathBuf::from("test.rs"),
+            &active,
+            GraphOptions::default(),
+        )
+        .expect("impl should still render, greyed out");
+
+        assert!(svg.contains("trait-node-inactive"));
+        assert!(svg.contains("cfg(test)"));
+    }
 
-        let metadata = &rels.functions["my_function"];
-        assert_eq!(metadata.name, "my_function");
-        assert_eq!(metadata.fully_qualified_name, "my_function");
-        assert!(!metadata.is_method);
-        assert!(metadata.is_public);
-        assert_eq!(metadata.file_path, PathBuf::from("test.rs"));
+    #[test]
+    fn test_cfg_predicate_satisfied_plain_flag() {
+        let active: HashSet<String> = ["test".to_string()].into_iter().collect();
+        assert!(cfg_predicate_satisfied(&Some("test".to_string()), &active));
+        assert!(!cfg_predicate_satisfied(&Some("unix".to_string()), &active));
     }
 
     #[test]
-    fn test_generate_call_graph_with_calls() {
-        let code = r#"
-            fn foo() { bar(); }
-            fn bar() {}
-        "#;
+    fn test_cfg_predicate_satisfied_not() {
+        let active: HashSet<String> = HashSet::new();
+        assert!(cfg_predicate_satisfied(&Some("not(test)".to_string()), &active));
 
-        let rels = parse_and_extract(code);
-        let svg = generate_function_call_graph("foo", &rels);
+        let active: HashSet<String> = ["test".to_string()].into_iter().collect();
+        assert!(!cfg_predicate_satisfied(&Some("not(test)".to_string()), &active));
+    }
 
-        assert!(svg.is_some());
-        let svg_content = svg.unwrap();
-        assert!(svg_content.contains("<svg"));
-        assert!(svg_content.contains("foo"));
-        assert!(svg_content.contains("bar"));
+    #[test]
+    fn test_cfg_predicate_satisfied_any_and_all() {
+        let active: HashSet<String> = ["unix".to_string()].into_iter().collect();
+        assert!(cfg_predicate_satisfied(&Some("any(unix, windows)".to_string()), &active));
+        assert!(!cfg_predicate_satisfied(&Some("all(unix, windows)".to_string()), &active));
     }
 
     #[test]
-    fn test_generate_call_graph_no_calls() {
+    fn test_cfg_predicate_satisfied_key_value() {
+        let active: HashSet<String> = ["feature=\"extra\"".to_string()].into_iter().collect();
+        assert!(cfg_predicate_satisfied(
+            &Some("feature = \"extra\"".to_string()),
+            &active
+        ));
+        assert!(!cfg_predicate_satisfied(
+            &Some("feature = \"missing\"".to_string()),
+            &active
+        ));
+    }
+
+    #[test]
+    fn test_cfg_predicate_satisfied_none_always_passes() {
+        assert!(cfg_predicate_satisfied(&None, &HashSet::new()));
+    }
+
+    #[test]
+    fn test_generate_function_call_graph_filters_inactive_cfg_callee() {
         let code = r#"
-            fn standalone() {
-                let x = 42;
-            }
+            fn caller() { gated(); }
+
+            #[cfg(test)]
+            fn gated() {}
         "#;
 
         let rels = parse_and_extract(code);
-        let svg = generate_function_call_graph("standalone", &rels);
 
-        assert!(svg.is_none());
+        let without_test = generate_function_call_graph("caller", &rels, &PathBuf::from("test.rs"), &HashSet::new());
+        assert!(without_test.is_none(), "caller has no active-cfg callees left to draw");
+
+        let active: HashSet<String> = ["test".to_string()].into_iter().collect();
+        let with_test = generate_function_call_graph("caller", &rels, &PathBuf::from("test.rs"), &active).unwrap();
+        assert!(with_test.contains("gated"));
     }
 
     #[test]
-    fn test_generate_call_graph_nonexistent_function() {
+    fn test_generate_type_inheritance_graph_filters_inactive_cfg_impl() {
         let code = r#"
-            fn foo() {}
+            struct MyStruct;
+
+            #[cfg(test)]
+            impl MyStruct {
+                fn foo(&self) {}
+            }
         "#;
 
         let rels = parse_and_extract(code);
-        let svg = generate_function_call_graph("nonexistent", &rels);
 
-        assert!(svg.is_none());
+        assert!(
+            generate_type_inheritance_graph("MyStruct", &rels, &PathBuf::from("test.rs"), &HashSet::new(), GraphOptions::default()).is_none()
+        );
+
+        let active: HashSet<String> = ["test".to_string()].into_iter().collect();
+        assert!(generate_type_inheritance_graph("MyStruct", &rels, &PathBuf::from("test.rs"), &active, GraphOptions::default()).is_some());
     }
 
     #[test]
-    fn test_multiple_calls() {
+    fn test_inheritance_graph_svg_structure() {
         let code = r#"
-            fn foo() {
-                bar();
-                baz();
-                qux();
+            trait MyTrait {
+                fn test(&self);
+            }
+
+            struct MyType;
+
+            impl MyTrait for MyType {
+                fn test(&self) {}
             }
-            fn bar() {}
-            fn baz() {}
-            fn qux() {}
         "#;
 
         let rels = parse_and_extract(code);
+        let svg = generate_type_inheritance_graph("MyType", &rels, &PathBuf::from("test.rs"), &HashSet::new(), GraphOptions::default());
 
-        assert_eq!(rels.call_graph["foo"].len(), 3);
-        assert!(rels.call_graph["foo"].contains("bar"));
-        assert!(rels.call_graph["foo"].contains("baz"));
-        assert!(rels.call_graph["foo"].contains("qux"));
+        assert!(svg.is_some());
+        let svg_content = svg.unwrap();
+
+        // Verify SVG structure
+        assert!(svg_content.contains("<svg"));
+        assert!(svg_content.contains("</svg>"));
+        assert!(svg_content.contains("<style>"));
+        assert!(svg_content.contains("<defs>"));
+        assert!(svg_content.contains("<rect"));
+        assert!(svg_content.contains("<text"));
+        assert!(svg_content.contains("<line"));
+        assert!(svg_content.contains("impl-edge"));
+        assert!(svg_content.contains("trait-node"));
+        assert!(svg_content.contains("type-node"));
     }
 
     #[test]
-    fn test_call_chain() {
+    fn test_inheritance_combined_trait_and_inherent() {
         let code = r#"
-            fn a() { b(); }
-            fn b() { c(); }
-            fn c() {}
+            trait Greet {
+                fn greet(&self);
+            }
+
+            struct Person;
+
+            impl Greet for Person {
+                fn greet(&self) {}
+            }
+
+            impl Person {
+                fn new() -> Self {
+                    Person
+                }
+            }
         "#;
 
         let rels = parse_and_extract(code);
 
-        assert!(rels.call_graph["a"].contains("b"));
-        assert!(rels.call_graph["b"].contains("c"));
-        assert!(rels.usage_graph["b"].contains("a"));
-        assert!(rels.usage_graph["c"].contains("b"));
+        // Should have both trait impl and inherent impl
+        assert!(rels.inheritance.contains_key("Person::Greet"));
+        assert!(rels.inheritance.contains_key("Person"));
+
+        let svg = generate_type_inheritance_graph("Person", &rels, &PathBuf::from("test.rs"), &HashSet::new(), GraphOptions::default());
+        assert!(svg.is_some());
+
+        let svg_content = svg.unwrap();
+        assert!(svg_content.contains("Person"));
+        assert!(svg_content.contains("Greet"));
     }
 
     #[test]
-    fn test_trait_impl() {
+    fn test_inheritance_qualified_type_name() {
         let code = r#"
-            trait MyTrait {
-                fn trait_method(&self);
+            mod inner {
+                pub struct MyType;
             }
 
-            struct MyStruct;
-
-            impl MyTrait for MyStruct {
-                fn trait_method(&self) {
-                    helper();
-                }
+            trait MyTrait {
+                fn test(&self);
             }
 
-            fn helper() {}
+            impl MyTrait for inner::MyType {
+                fn test(&self) {}
+            }
         "#;
 
         let rels = parse_and_extract(code);
 
-        assert!(rels.functions.contains_key("MyStruct::trait_method"));
-        assert!(rels.inheritance.contains_key("MyStruct::MyTrait"));
+        // The type name should include the module path
+        let has_qualified = rels
+            .inheritance
+            .values()
+            .any(|info| info.type_name.contains("inner"));
+
+        assert!(has_qualified);
     }
 
     #[test]
-    fn test_nested_calls() {
+    fn test_inheritance_collapses_use_aliased_impl() {
         let code = r#"
-            fn outer() {
-                inner(middle());
+            mod inner {
+                pub struct MyType;
+            }
+            use inner::MyType as M;
+
+            trait MyTrait {
+                fn test(&self);
+                fn other(&self);
+            }
+
+            impl MyTrait for inner::MyType {
+                fn test(&self) {}
+                fn other(&self) {}
+            }
+
+            impl MyTrait for M {
+                fn test(&self) {}
+                fn other(&self) {}
             }
-            fn middle() {}
-            fn inner(x: ()) {}
         "#;
 
         let rels = parse_and_extract(code);
 
-        assert!(rels.call_graph["outer"].contains("middle"));
-        assert!(rels.call_graph["outer"].contains("inner"));
-        assert_eq!(rels.call_graph["outer"].len(), 2);
+        // Both impls name the same underlying type once the alias is resolved, so
+        // they should collapse into a single inheritance entry rather than one per
+        // spelling.
+        let my_type_impls: Vec<_> = rels
+            .inheritance
+            .values()
+            .filter(|info| info.type_name.contains("MyType"))
+            .collect();
+
+        assert_eq!(my_type_impls.len(), 1);
     }
 
     #[test]
-    fn test_self_recursion() {
+    fn test_extract_relationships_populates_verus_contracts() {
         let code = r#"
-            fn recursive(n: i32) {
-                if n > 0 {
-                    recursive(n - 1);
+            verus! {
+                pub fn sum_two(a: u32, b: u32) -> (result: u64)
+                    requires
+                        a <= 1000000,
+                    ensures
+                        result == (a as u64) + (b as u64),
+                {
+                    (a as u64) + (b as u64)
                 }
             }
         "#;
 
         let rels = parse_and_extract(code);
 
-        assert!(rels.call_graph.contains_key("recursive"));
-        assert!(rels.call_graph["recursive"].contains("recursive"));
-        assert!(rels.usage_graph["recursive"].contains("recursive"));
+        let contract = &rels.verus_contracts["sum_two"];
+        assert_eq!(contract.requires.len(), 1);
+        assert_eq!(contract.ensures.len(), 1);
     }
 
     #[test]
-    fn test_mutual_recursion() {
+    fn test_cfg_straight_line_function_has_single_body_block() {
         let code = r#"
-            fn foo(n: i32) {
-                if n > 0 { bar(n - 1); }
-            }
-            fn bar(n: i32) {
-                if n > 0 { foo(n - 1); }
+            fn foo() {
+                let x = 1;
+                bar();
             }
+            fn bar() {}
         "#;
 
         let rels = parse_and_extract(code);
+        let cfg = &rels.cfgs["foo"];
 
-        assert!(rels.call_graph["foo"].contains("bar"));
-        assert!(rels.call_graph["bar"].contains("foo"));
+        // No branches: everything accumulates into the entry block, which then
+        // edges straight to the exit block.
+        assert_eq!(cfg.blocks.len(), 2);
+        assert_eq!(cfg.blocks[cfg.entry].successors.len(), 1);
+        assert!(cfg.blocks[cfg.entry].statements.iter().any(|s| s.contains("bar")));
     }
 
     #[test]
-    fn test_qualified_path_call() {
+    fn test_cfg_if_else_creates_then_and_else_branches() {
         let code = r#"
-            fn test() {
-                std::mem::drop(42);
+            fn foo(cond: bool) {
+                if cond {
+                    bar();
+                } else {
+                    baz();
+                }
             }
+            fn bar() {}
+            fn baz() {}
         "#;
 
         let rels = parse_and_extract(code);
+        let cfg = &rels.cfgs["foo"];
 
-        assert!(rels.call_graph.contains_key("test"));
-        assert!(rels.call_graph["test"].contains("std::mem::drop"));
+        let branch_block = cfg
+            .blocks
+            .iter()
+            .find(|b| b.successors.len() == 2)
+            .expect("branch block with then/else successors");
+
+        let labels: Vec<_> = branch_block
+            .successors
+            .iter()
+            .filter_map(|e| e.label.clone())
+            .collect();
+        assert!(labels.contains(&"then".to_string()));
+        assert!(labels.contains(&"else".to_string()));
     }
 
     #[test]
-    fn test_method_on_type() {
+    fn test_cfg_if_without_else_adds_implicit_else_edge() {
         let code = r#"
-            struct Foo;
-            impl Foo {
-                fn new() -> Self { Foo }
-                fn method(&self) {
-                    Self::new();
+            fn foo(cond: bool) {
+                if cond {
+                    bar();
                 }
             }
+            fn bar() {}
         "#;
 
         let rels = parse_and_extract(code);
+        let cfg = &rels.cfgs["foo"];
+
+        let branch_block = cfg
+            .blocks
+            .iter()
+            .find(|b| b.successors.len() == 2)
+            .expect("branch block with then/implicit-else successors");
 
-        assert!(rels.functions.contains_key("Foo::new"));
-        assert!(rels.functions.contains_key("Foo::method"));
-        assert!(rels.call_graph["Foo::method"].contains("Self::new"));
+        let labels: Vec<_> = branch_block
+            .successors
+            .iter()
+            .filter_map(|e| e.label.clone())
+            .collect();
+        assert!(labels.contains(&"else".to_string()));
     }
 
     #[test]
-    fn test_closure_calls() {
+    fn test_cfg_match_fans_out_one_successor_per_arm() {
         let code = r#"
-            fn outer() {
-                let closure = || {
-                    inner();
-                };
-                closure();
+            fn foo(x: u32) {
+                match x {
+                    0 => bar(),
+                    _ => baz(),
+                }
             }
-            fn inner() {}
+            fn bar() {}
+            fn baz() {}
         "#;
 
         let rels = parse_and_extract(code);
+        let cfg = &rels.cfgs["foo"];
 
-        // Closures are captured within the outer function's scope
-        assert!(rels.call_graph.contains_key("outer"));
-        assert!(rels.call_graph["outer"].contains("inner"));
+        let match_block = cfg
+            .blocks
+            .iter()
+            .find(|b| b.successors.len() == 2)
+            .expect("match block with two arm successors");
+        assert_eq!(match_block.successors.len(), 2);
     }
 
     #[test]
-    fn test_generic_function() {
+    fn test_cfg_while_loop_has_back_edge_and_exit_edge() {
         let code = r#"
-            fn generic<T>(x: T) {
-                helper();
+            fn foo() {
+                let mut i = 0;
+                while i < 10 {
+                    i = i + 1;
+                }
             }
-            fn helper() {}
         "#;
 
         let rels = parse_and_extract(code);
+        let cfg = &rels.cfgs["foo"];
 
-        assert!(rels.functions.contains_key("generic"));
-        assert!(rels.call_graph["generic"].contains("helper"));
+        let header = cfg
+            .blocks
+            .iter()
+            .find(|b| b.successors.len() == 2)
+            .expect("loop header with body/exit successors");
+        let labels: Vec<_> = header.successors.iter().filter_map(|e| e.label.clone()).collect();
+        assert!(labels.contains(&"loop".to_string()));
+        assert!(labels.contains(&"exit".to_string()));
+
+        // The body's last block must edge back to the header (the back-edge).
+        let header_id = header.id;
+        assert!(
+            cfg.blocks
+                .iter()
+                .any(|b| b.successors.iter().any(|e| e.target == header_id && e.label.is_none()))
+        );
     }
 
     #[test]
-    fn test_async_function() {
+    fn test_cfg_continue_and_break_target_loop_header_and_exit() {
         let code = r#"
-            async fn async_fn() {
-                other().await;
+            fn foo() {
+                while true {
+                    if cond() {
+                        continue;
+                    }
+                    if other() {
+                        break;
+                    }
+                }
             }
-            async fn other() {}
+            fn cond() -> bool { true }
+            fn other() -> bool { true }
         "#;
 
         let rels = parse_and_extract(code);
+        let cfg = &rels.cfgs["foo"];
+
+        let has_continue_edge = cfg
+            .blocks
+            .iter()
+            .any(|b| b.successors.iter().any(|e| e.label.as_deref() == Some("continue")));
+        let has_break_edge = cfg
+            .blocks
+            .iter()
+            .any(|b| b.successors.iter().any(|e| e.label.as_deref() == Some("break")));
 
-        assert!(rels.functions.contains_key("async_fn"));
-        assert!(rels.call_graph["async_fn"].contains("other"));
+        assert!(has_continue_edge);
+        assert!(has_break_edge);
     }
 
     #[test]
-    fn test_const_function() {
+    fn test_cfg_return_edges_to_exit_block() {
         let code = r#"
-            const fn const_fn() {
-                helper();
+            fn foo(cond: bool) {
+                if cond {
+                    return;
+                }
+                bar();
             }
-            const fn helper() {}
+            fn bar() {}
         "#;
 
         let rels = parse_and_extract(code);
-
-        assert!(rels.functions.contains_key("const_fn"));
-        assert!(rels.call_graph["const_fn"].contains("helper"));
+        let cfg = &rels.cfgs["foo"];
+        let exit = cfg.exit;
+
+        let has_return_edge = cfg.blocks.iter().any(|b| {
+            b.successors
+                .iter()
+                .any(|e| e.target == exit && e.label.as_deref() == Some("return"))
+        });
+        assert!(has_return_edge);
     }
 
     #[test]
-    fn test_multiple_impls_same_type() {
+    fn test_cfg_stored_for_impl_method_qualified_by_type() {
         let code = r#"
             struct Foo;
-
-            impl Foo {
-                fn method1(&self) {}
-            }
-
             impl Foo {
-                fn method2(&self) {}
+                fn bar(&self) {
+                    let x = 1;
+                }
             }
         "#;
 
         let rels = parse_and_extract(code);
-
-        assert!(rels.functions.contains_key("Foo::method1"));
-        assert!(rels.functions.contains_key("Foo::method2"));
+        assert!(rels.cfgs.contains_key("Foo::bar"));
     }
 
     #[test]
-    fn test_empty_function() {
+    fn test_generate_function_cfg_graph_renders_blocks() {
         let code = r#"
-            fn empty() {}
+            fn foo(cond: bool) {
+                if cond {
+                    bar();
+                } else {
+                    baz();
+                }
+            }
+            fn bar() {}
+            fn baz() {}
         "#;
 
         let rels = parse_and_extract(code);
+        let svg = generate_function_cfg_graph("foo", &rels, &PathBuf::from("fn.foo.html"));
 
-        assert_eq!(rels.functions.len(), 1);
-        assert!(!rels.call_graph.contains_key("empty"));
-        assert!(!rels.usage_graph.contains_key("empty"));
+        assert!(svg.is_some());
+        let svg_content = svg.unwrap();
+        assert!(svg_content.contains("<svg"));
+        assert!(svg_content.contains("then"));
+        assert!(svg_content.contains("else"));
     }
 
     #[test]
-    fn test_call_graph_with_callers_and_callees() {
+    fn test_generate_function_cfg_graph_nonexistent_function() {
+        let rels = CodeRelationships::default();
+        let svg = generate_function_cfg_graph("missing", &rels, &PathBuf::from("test.rs"));
+        assert!(svg.is_none());
+    }
+
+    #[test]
+    fn test_visit_item_mod_qualifies_nested_function_names() {
         let code = r#"
-            fn caller1() { middle(); }
-            fn caller2() { middle(); }
-            fn middle() { callee(); }
-            fn callee() {}
+            mod outer {
+                mod inner {
+                    fn helper() {}
+                }
+            }
         "#;
 
         let rels = parse_and_extract(code);
-        let svg = generate_function_call_graph("middle", &rels);
-
-        assert!(svg.is_some());
-        let svg_content = svg.unwrap();
-
-        // Should contain both callers and callees
-        assert!(svg_content.contains("caller1") || svg_content.contains("caller2"));
-        assert!(svg_content.contains("callee"));
-        assert!(svg_content.contains("middle"));
-
-        // Should have caller-edge class for incoming edges
-        assert!(svg_content.contains("caller-edge"));
 
-        // Should have regular edge class for outgoing edges
-        assert!(svg_content.contains("class=\"edge\""));
+        assert!(rels.functions.contains_key("outer::inner::helper"));
+        assert!(!rels.functions.contains_key("helper"));
     }
 
     #[test]
-    fn test_function_with_only_callers() {
+    fn test_use_import_resolves_bare_call_to_full_path() {
         let code = r#"
-            fn caller1() { target(); }
-            fn caller2() { target(); }
-            fn target() {}
+            use std::collections::BTreeMap;
+
+            fn make() {
+                BTreeMap::new();
+            }
         "#;
 
         let rels = parse_and_extract(code);
-        let svg = generate_function_call_graph("target", &rels);
-
-        assert!(svg.is_some());
-        let svg_content = svg.unwrap();
-        assert!(svg_content.contains("caller"));
-        assert!(svg_content.contains("target"));
+        assert!(rels.call_graph["make"].iter().any(|(n, _)| n == "std::collections::BTreeMap::new"));
     }
 
     #[test]
-    fn test_function_with_only_callees() {
+    fn test_use_rename_resolves_to_aliased_target() {
         let code = r#"
-            fn caller() {
-                callee1();
-                callee2();
+            use std::collections::HashMap as Map;
+
+            fn make() {
+                Map::new();
             }
-            fn callee1() {}
-            fn callee2() {}
         "#;
 
         let rels = parse_and_extract(code);
-        let svg = generate_function_call_graph("caller", &rels);
-
-        assert!(svg.is_some());
-        let svg_content = svg.unwrap();
-        assert!(svg_content.contains("callee"));
-        assert!(svg_content.contains("caller"));
+        assert!(rels.call_graph["make"].iter().any(|(n, _)| n == "std::collections::HashMap::new"));
     }
 
     #[test]
-    fn test_extract_path_name() {
+    fn test_ancestor_module_sibling_function_resolves_correctly() {
         let code = r#"
-            fn test() {
-                std::collections::HashMap::new();
+            mod util {
+                fn caller() {
+                    helper();
+                }
+
+                fn helper() {}
             }
         "#;
 
         let rels = parse_and_extract(code);
-
-        assert!(rels.call_graph.contains_key("test"));
-        assert!(rels.call_graph["test"].contains("std::collections::HashMap::new"));
+        assert!(rels.call_graph["util::caller"].iter().any(|(n, _)| n == "util::helper"));
     }
 
     #[test]
-    fn test_method_is_marked_as_method() {
+    fn test_glob_import_is_recorded_but_does_not_resolve_bare_calls() {
         let code = r#"
-            struct Foo;
-            impl Foo {
-                fn is_method(&self) {}
+            use std::collections::*;
+
+            fn make() {
+                something();
             }
-            fn is_function() {}
         "#;
 
         let rels = parse_and_extract(code);
-
-        assert!(rels.functions["Foo::is_method"].is_method);
-        assert!(!rels.functions["is_function"].is_method);
+        assert!(rels.call_graph["make"].iter().any(|(n, _)| n == "something"));
     }
 
     #[test]
-    fn test_turbofish_syntax() {
+    fn test_unresolved_external_call_falls_back_to_raw_name() {
         let code = r#"
             fn caller() {
-                helper::<i32>();
+                totally_unknown();
             }
-            fn helper<T>() {}
         "#;
 
         let rels = parse_and_extract(code);
-
-        assert!(rels.call_graph["caller"].contains("helper"));
+        assert!(rels.call_graph["caller"].iter().any(|(n, _)| n == "totally_unknown"));
     }
 
     #[test]
-    fn test_match_with_calls() {
+    fn test_nested_use_group_with_rename() {
         let code = r#"
-            fn test(x: Option<i32>) {
-                match x {
-                    Some(_) => handle_some(),
-                    None => handle_none(),
-                }
+            use std::{fmt, collections::HashMap as Map};
+
+            fn make() {
+                Map::new();
             }
-            fn handle_some() {}
-            fn handle_none() {}
         "#;
 
         let rels = parse_and_extract(code);
-
-        assert!(rels.call_graph["test"].contains("handle_some"));
-        assert!(rels.call_graph["test"].contains("handle_none"));
+        assert!(rels.call_graph["make"].iter().any(|(n, _)| n == "std::collections::HashMap::new"));
     }
 
     #[test]
-    fn test_if_else_with_calls() {
+    fn test_export_relationships_json_round_trips_through_import() {
         let code = r#"
-            fn test(condition: bool) {
-                if condition {
-                    branch_true();
-                } else {
-                    branch_false();
+            trait Greeter {
+                fn greet(&self);
+            }
+            struct Bot;
+            impl Greeter for Bot {
+                fn greet(&self) {
+                    helper();
                 }
             }
-            fn branch_true() {}
-            fn branch_false() {}
+            fn helper() {}
         "#;
 
         let rels = parse_and_extract(code);
+        let json = export_relationships_json(&rels);
+        let reloaded = import_relationships_json(&json).expect("valid JSON");
 
-        assert!(rels.call_graph["test"].contains("branch_true"));
-        assert!(rels.call_graph["test"].contains("branch_false"));
+        assert_eq!(reloaded.call_graph, rels.call_graph);
+        assert_eq!(reloaded.usage_graph, rels.usage_graph);
+        assert_eq!(reloaded.functions.len(), rels.functions.len());
+        assert_eq!(reloaded.inheritance.len(), rels.inheritance.len());
     }
 
     #[test]
-    fn test_loop_with_calls() {
+    fn test_export_relationships_json_uses_stable_top_level_keys() {
+        let rels = CodeRelationships::default();
+        let json = export_relationships_json(&rels);
+        let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+
+        let object = value.as_object().unwrap();
+        assert!(object.contains_key("functions"));
+        assert!(object.contains_key("call_graph"));
+        assert!(object.contains_key("usage_graph"));
+        assert!(object.contains_key("inheritance"));
+    }
+
+    #[test]
+    fn test_import_relationships_json_rejects_malformed_input() {
+        assert!(import_relationships_json("not json").is_err());
+    }
+
+    #[test]
+    fn test_generate_index_lists_types_traits_and_methods_once_each() {
         let code = r#"
-            fn test() {
-                loop {
-                    if condition() {
-                        break;
-                    }
-                    action();
-                }
+            trait Greeter {
+                fn greet(&self);
+            }
+            struct Bot;
+            impl Greeter for Bot {
+                fn greet(&self) {}
+            }
+            impl Bot {
+                fn wave(&self) {}
             }
-            fn condition() -> bool { true }
-            fn action() {}
         "#;
 
         let rels = parse_and_extract(code);
+        let index = generate_index(&rels);
+
+        let types = index["types"].as_array().unwrap();
+        assert_eq!(types.len(), 1);
+        assert_eq!(types[0]["name"], "Bot");
 
-        assert!(rels.call_graph["test"].contains("condition"));
-        assert!(rels.call_graph["test"].contains("action"));
+        let traits = index["traits"].as_array().unwrap();
+        assert_eq!(traits.len(), 1);
+        assert_eq!(traits[0]["name"], "Greeter");
+
+        let methods = index["methods"].as_array().unwrap();
+        assert_eq!(methods.len(), 2);
+        assert!(methods.iter().any(|m| m["name"] == "greet" && m["owner"] == "Bot"));
+        assert!(methods.iter().any(|m| m["name"] == "wave" && m["owner"] == "Bot"));
     }
 
     #[test]
-    fn test_chained_method_calls() {
+    fn test_method_call_resolves_via_let_type_ascription() {
         let code = r#"
-            fn test() {
-                vec![1, 2, 3]
-                    .iter()
-                    .map()
-                    .collect();
+            struct Bot;
+            impl Bot {
+                fn greet(&self) {}
             }
+            fn caller() {
+                let bot: Bot = make_bot();
+                bot.greet();
+            }
+            fn make_bot() -> Bot { Bot }
         "#;
 
         let rels = parse_and_extract(code);
-
-        assert!(rels.call_graph.contains_key("test"));
-        assert!(rels.call_graph["test"].contains("iter"));
-        assert!(rels.call_graph["test"].contains("map"));
-        assert!(rels.call_graph["test"].contains("collect"));
+        assert!(rels.call_graph["caller"].iter().any(|(n, _)| n == "Bot::greet"));
     }
 
     #[test]
-    fn test_inheritance_graph_single_trait() {
+    fn test_method_call_resolves_via_associated_function_initializer() {
         let code = r#"
-            trait Greeter {
-                fn greet(&self);
-            }
-
-            struct FriendlyGreeter;
-
-            impl Greeter for FriendlyGreeter {
+            struct Bot;
+            impl Bot {
+                fn new() -> Self { Bot }
                 fn greet(&self) {}
             }
+            fn caller() {
+                let bot = Bot::new();
+                bot.greet();
+            }
         "#;
 
         let rels = parse_and_extract(code);
-        let svg = generate_type_inheritance_graph("FriendlyGreeter", &rels);
-
-        assert!(svg.is_some());
-        let svg_content = svg.unwrap();
-        assert!(svg_content.contains("<svg"));
-        assert!(svg_content.contains("FriendlyGreeter"));
-        assert!(svg_content.contains("Greeter"));
-        assert!(svg_content.contains("trait-node"));
-        assert!(svg_content.contains("type-node"));
+        assert!(rels.call_graph["caller"].iter().any(|(n, _)| n == "Bot::greet"));
     }
 
     #[test]
-    fn test_inheritance_graph_multiple_traits() {
+    fn test_method_call_resolves_via_struct_literal_initializer() {
         let code = r#"
-            trait Trait1 {
-                fn method1(&self);
+            struct Bot;
+            impl Bot {
+                fn greet(&self) {}
             }
-
-            trait Trait2 {
-                fn method2(&self);
+            fn caller() {
+                let bot = Bot {};
+                bot.greet();
             }
+        "#;
 
-            struct MyType;
-
-            impl Trait1 for MyType {
-                fn method1(&self) {}
-            }
+        let rels = parse_and_extract(code);
+        assert!(rels.call_graph["caller"].iter().any(|(n, _)| n == "Bot::greet"));
+    }
 
-            impl Trait2 for MyType {
-                fn method2(&self) {}
+    #[test]
+    fn test_self_method_call_resolves_to_enclosing_type() {
+        let code = r#"
+            struct Bot;
+            impl Bot {
+                fn greet(&self) {
+                    self.helper();
+                }
+                fn helper(&self) {}
             }
         "#;
 
         let rels = parse_and_extract(code);
-        let svg = generate_type_inheritance_graph("MyType", &rels);
-
-        assert!(svg.is_some());
-        let svg_content = svg.unwrap();
-        assert!(svg_content.contains("MyType"));
-        assert!(svg_content.contains("Trait1"));
-        assert!(svg_content.contains("Trait2"));
+        assert!(rels.call_graph["Bot::greet"].iter().any(|(n, _)| n == "Bot::helper"));
     }
 
     #[test]
-    fn test_inheritance_graph_no_traits() {
+    fn test_resolved_method_call_also_records_trait_qualified_form() {
         let code = r#"
-            struct PlainStruct;
-
-            impl PlainStruct {
-                fn method(&self) {}
+            trait Greeter {
+                fn greet(&self);
+            }
+            struct Bot;
+            impl Greeter for Bot {
+                fn greet(&self) {}
+            }
+            fn caller() {
+                let bot = Bot {};
+                bot.greet();
             }
         "#;
 
-        let rels = parse_and_extract(code);
-        let svg = generate_type_inheritance_graph("PlainStruct", &rels);
-
-        // Should return Some because there's an inherent impl
-        assert!(svg.is_some());
+        let rels = parse_and_extract(code);
+        assert!(rels.call_graph["caller"].iter().any(|(n, _)| n == "Bot::greet"));
+        assert!(rels.call_graph["caller"].iter().any(|(n, _)| n == "Greeter::greet"));
     }
 
     #[test]
-    fn test_inheritance_graph_nonexistent_type() {
+    fn test_unresolved_receiver_falls_back_to_bare_method_name() {
         let code = r#"
-            struct Foo;
+            fn caller(items: Vec<i32>) {
+                items.iter().sum::<i32>();
+            }
         "#;
 
         let rels = parse_and_extract(code);
-        let svg = generate_type_inheritance_graph("NonExistent", &rels);
-
-        assert!(svg.is_none());
+        assert!(rels.call_graph["caller"].iter().any(|(n, _)| n == "iter"));
     }
 
     #[test]
-    fn test_inheritance_info_stored() {
+    fn test_inner_block_let_does_not_leak_into_outer_scope() {
         let code = r#"
-            trait MyTrait {
-                fn trait_method(&self);
+            struct Bot;
+            impl Bot {
+                fn greet(&self) {}
             }
-
-            struct MyStruct;
-
-            impl MyTrait for MyStruct {
-                fn trait_method(&self) {}
+            fn caller() {
+                {
+                    let bot = Bot {};
+                    bot.greet();
+                }
+                bot.greet();
             }
         "#;
 
         let rels = parse_and_extract(code);
-
-        assert!(rels.inheritance.contains_key("MyStruct::MyTrait"));
-        let info = &rels.inheritance["MyStruct::MyTrait"];
-        assert_eq!(info.type_name, "MyStruct");
-        assert_eq!(info.trait_name, Some("MyTrait".to_string()));
-        assert_eq!(info.methods.len(), 1);
-        assert!(info.methods.contains(&"trait_method".to_string()));
+        assert!(rels.call_graph["caller"].iter().any(|(n, _)| n == "Bot::greet"));
+        assert!(rels.call_graph["caller"].iter().any(|(n, _)| n == "greet"));
     }
 
     #[test]
-    fn test_inheritance_graph_with_enum() {
+    fn test_outer_let_is_visible_inside_nested_block() {
         let code = r#"
-            trait Handler {
-                fn handle(&self);
-            }
-
-            enum Event {
-                Click,
-                Hover,
+            struct Bot;
+            impl Bot {
+                fn greet(&self) {}
             }
-
-            impl Handler for Event {
-                fn handle(&self) {}
+            fn caller() {
+                let bot = Bot {};
+                {
+                    bot.greet();
+                }
             }
         "#;
 
         let rels = parse_and_extract(code);
-        let svg = generate_type_inheritance_graph("Event", &rels);
-
-        assert!(svg.is_some());
-        let svg_content = svg.unwrap();
-        assert!(svg_content.contains("Event"));
-        assert!(svg_content.contains("Handler"));
+        assert!(rels.call_graph["caller"].iter().any(|(n, _)| n == "Bot::greet"));
     }
 
     #[test]
-    fn test_inheritance_graph_many_methods() {
+    fn test_closure_param_type_ascription_resolves_method_call() {
         let code = r#"
-            trait LargeTrait {
-                fn method1(&self);
-                fn method2(&self);
-                fn method3(&self);
-                fn method4(&self);
-                fn method5(&self);
+            struct Bot;
+            impl Bot {
+                fn greet(&self) {}
             }
-
-            struct MyType;
-
-            impl LargeTrait for MyType {
-                fn method1(&self) {}
-                fn method2(&self) {}
-                fn method3(&self) {}
-                fn method4(&self) {}
-                fn method5(&self) {}
+            fn caller() {
+                let run = |bot: Bot| {
+                    bot.greet();
+                };
             }
         "#;
 
         let rels = parse_and_extract(code);
-        let svg = generate_type_inheritance_graph("MyType", &rels);
-
-        assert!(svg.is_some());
-        let svg_content = svg.unwrap();
-        // Should show "5 methods" instead of listing all
-        assert!(svg_content.contains("5 methods"));
+        assert!(rels.call_graph["caller"].iter().any(|(n, _)| n == "Bot::greet"));
     }
 
     #[test]
-    fn test_inheritance_graph_few_methods_listed() {
+    fn test_closure_param_does_not_leak_into_enclosing_scope() {
         let code = r#"
-            trait SmallTrait {
-                fn foo(&self);
-                fn bar(&self);
+            struct Bot;
+            impl Bot {
+                fn greet(&self) {}
             }
-
-            struct MyType;
-
-            impl SmallTrait for MyType {
-                fn foo(&self) {}
-                fn bar(&self) {}
+            fn caller() {
+                let run = |bot: Bot| {
+                    bot.greet();
+                };
+                bot.greet();
             }
         "#;
 
         let rels = parse_and_extract(code);
-        let svg = generate_type_inheritance_graph("MyType", &rels);
-
-        assert!(svg.is_some());
-        let svg_content = svg.unwrap();
-        // Should list method names for 3 or fewer
-        assert!(svg_content.contains("foo"));
-        assert!(svg_content.contains("bar"));
+        assert!(rels.call_graph["caller"].iter().any(|(n, _)| n == "greet"));
     }
 
     #[test]
-    fn test_inheritance_graph_generic_trait() {
+    fn test_reassignment_updates_inferred_type() {
         let code = r#"
-            trait Convert<T> {
-                fn convert(&self) -> T;
+            struct Bot;
+            impl Bot {
+                fn greet(&self) {}
             }
-
-            struct MyType;
-
-            impl Convert<String> for MyType {
-                fn convert(&self) -> String {
-                    String::new()
-                }
+            struct Other;
+            impl Other {
+                fn greet(&self) {}
+            }
+            fn caller() {
+                let mut bot = Bot {};
+                bot.greet();
+                bot = Other {};
+                bot.greet();
             }
         "#;
 
         let rels = parse_and_extract(code);
-        let svg = generate_type_inheritance_graph("MyType", &rels);
-
-        assert!(svg.is_some());
-        let svg_content = svg.unwrap();
-        assert!(svg_content.contains("MyType"));
-        assert!(svg_content.contains("Convert"));
+        assert!(rels.call_graph["caller"].iter().any(|(n, _)| n == "Bot::greet"));
+        assert!(rels.call_graph["caller"].iter().any(|(n, _)| n == "Other::greet"));
     }
 
     #[test]
-    fn test_inheritance_graph_std_trait() {
+    fn test_reassignment_with_unresolvable_rhs_drops_to_unknown() {
         let code = r#"
-            struct MyType;
-
-            impl std::fmt::Display for MyType {
-                fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
-                    Ok(())
-                }
+            struct Bot;
+            impl Bot {
+                fn greet(&self) {}
+            }
+            fn caller(other: Bot) {
+                let bot = Bot {};
+                bot = other;
+                bot.greet();
             }
         "#;
 
         let rels = parse_and_extract(code);
-        let svg = generate_type_inheritance_graph("MyType", &rels);
-
-        assert!(svg.is_some());
-        let svg_content = svg.unwrap();
-        assert!(svg_content.contains("MyType"));
-        // Should show simple name, not full path
-        assert!(svg_content.contains("Display"));
+        assert!(rels.call_graph["caller"].iter().any(|(n, _)| n == "greet"));
+        assert!(!rels.call_graph["caller"].iter().any(|(n, _)| n == "Bot::greet"));
     }
 
     #[test]
-    fn test_inheritance_multiple_impls_same_trait() {
+    fn test_generate_function_call_graph_dot_emits_edges() {
         let code = r#"
-            struct TypeA;
-            struct TypeB;
-
-            trait Common {
-                fn common(&self);
-            }
-
-            impl Common for TypeA {
-                fn common(&self) {}
-            }
-
-            impl Common for TypeB {
-                fn common(&self) {}
-            }
+            fn caller1() { middle(); }
+            fn middle() { callee(); }
+            fn callee() {}
         "#;
 
         let rels = parse_and_extract(code);
+        let dot = generate_function_call_graph_dot("middle", &rels, &HashSet::new()).expect("middle has callers and callees");
 
-        // Each type should have its own graph
-        let svg_a = generate_type_inheritance_graph("TypeA", &rels);
-        let svg_b = generate_type_inheritance_graph("TypeB", &rels);
-
-        assert!(svg_a.is_some());
-        assert!(svg_b.is_some());
-
-        let content_a = svg_a.unwrap();
-        let content_b = svg_b.unwrap();
-
-        assert!(content_a.contains("TypeA"));
-        assert!(content_a.contains("Common"));
+        assert!(dot.starts_with("digraph G {"));
+        assert!(dot.contains("\"caller1\" -> \"middle\";"));
+        assert!(dot.contains("\"middle\" -> \"callee\";"));
+    }
 
-        assert!(content_b.contains("TypeB"));
-        assert!(content_b.contains("Common"));
+    #[test]
+    fn test_generate_function_call_graph_dot_nonexistent_function() {
+        let rels = CodeRelationships::default();
+        assert!(generate_function_call_graph_dot("missing", &rels, &HashSet::new()).is_none());
     }
 
     #[test]
-    fn test_inheritance_trait_with_no_methods() {
+    fn test_compute_reachable_functions_from_pub_root() {
         let code = r#"
-            trait Marker {}
-
-            struct MyType;
-
-            impl Marker for MyType {}
+            pub fn entry() {
+                helper();
+            }
+            fn helper() {
+                dead_end();
+            }
+            fn unused() {}
+            fn dead_end() {}
         "#;
 
         let rels = parse_and_extract(code);
-        let svg = generate_type_inheritance_graph("MyType", &rels);
+        let reachable = compute_reachable_functions(&rels);
 
-        assert!(svg.is_some());
-        let svg_content = svg.unwrap();
-        assert!(svg_content.contains("MyType"));
-        assert!(svg_content.contains("Marker"));
+        assert!(reachable.contains("entry"));
+        assert!(reachable.contains("helper"));
+        assert!(reachable.contains("dead_end"));
+        assert!(!reachable.contains("unused"));
     }
 
     #[test]
-    fn test_inherent_impl_stored() {
+    fn test_compute_reachable_functions_includes_main_and_trait_methods() {
         let code = r#"
-            struct MyStruct;
-
-            impl MyStruct {
-                fn new() -> Self {
-                    MyStruct
+            trait Greeter {
+                fn greet(&self);
+            }
+            struct Bot;
+            impl Greeter for Bot {
+                fn greet(&self) {
+                    helper();
                 }
-                fn method(&self) {}
             }
+            fn helper() {}
+            fn main() {}
         "#;
 
         let rels = parse_and_extract(code);
+        let reachable = compute_reachable_functions(&rels);
 
-        assert!(rels.inheritance.contains_key("MyStruct"));
-        let info = &rels.inheritance["MyStruct"];
-        assert_eq!(info.type_name, "MyStruct");
-        assert_eq!(info.trait_name, None);
-        assert_eq!(info.methods.len(), 2);
-        assert!(info.methods.contains(&"new".to_string()));
-        assert!(info.methods.contains(&"method".to_string()));
+        assert!(reachable.contains("main"));
+        assert!(reachable.contains("Bot::greet"));
+        assert!(reachable.contains("helper"));
     }
 
     #[test]
-    fn test_inheritance_graph_svg_structure() {
+    fn test_find_unreachable_functions_reports_sorted_dead_code() {
         let code = r#"
-            trait MyTrait {
-                fn test(&self);
-            }
-
-            struct MyType;
-
-            impl MyTrait for MyType {
-                fn test(&self) {}
-            }
+            pub fn entry() {}
+            fn zebra() {}
+            fn apple() {}
         "#;
 
         let rels = parse_and_extract(code);
-        let svg = generate_type_inheritance_graph("MyType", &rels);
-
-        assert!(svg.is_some());
-        let svg_content = svg.unwrap();
+        let unreachable = find_unreachable_functions(&rels);
 
-        // Verify SVG structure
-        assert!(svg_content.contains("<svg"));
-        assert!(svg_content.contains("</svg>"));
-        assert!(svg_content.contains("<style>"));
-        assert!(svg_content.contains("<defs>"));
-        assert!(svg_content.contains("<rect"));
-        assert!(svg_content.contains("<text"));
-        assert!(svg_content.contains("<line"));
-        assert!(svg_content.contains("impl-edge"));
-        assert!(svg_content.contains("trait-node"));
-        assert!(svg_content.contains("type-node"));
+        assert_eq!(unreachable, vec!["apple".to_string(), "zebra".to_string()]);
     }
 
     #[test]
-    fn test_inheritance_combined_trait_and_inherent() {
+    fn test_find_unreachable_functions_empty_when_all_reachable() {
         let code = r#"
-            trait Greet {
-                fn greet(&self);
+            pub fn entry() {
+                helper();
             }
+            fn helper() {}
+        "#;
 
-            struct Person;
-
-            impl Greet for Person {
-                fn greet(&self) {}
-            }
+        let rels = parse_and_extract(code);
+        assert!(find_unreachable_functions(&rels).is_empty());
+    }
 
-            impl Person {
-                fn new() -> Self {
-                    Person
-                }
+    #[test]
+    fn test_generate_reachability_graph_dims_unreachable_nodes() {
+        let code = r#"
+            pub fn entry() {
+                helper();
             }
+            fn helper() {}
+            fn unused() {}
         "#;
 
         let rels = parse_and_extract(code);
+        let svg = generate_reachability_graph(&rels, &HashSet::new()).expect("functions exist");
 
-        // Should have both trait impl and inherent impl
-        assert!(rels.inheritance.contains_key("Person::Greet"));
-        assert!(rels.inheritance.contains_key("Person"));
-
-        let svg = generate_type_inheritance_graph("Person", &rels);
-        assert!(svg.is_some());
+        assert!(svg.contains("class=\"unreachable\""));
+        assert!(svg.contains(">entry<"));
+        assert!(svg.contains(">helper<"));
+        assert!(svg.contains(">unused<"));
+    }
 
-        let svg_content = svg.unwrap();
-        assert!(svg_content.contains("Person"));
-        assert!(svg_content.contains("Greet"));
+    #[test]
+    fn test_generate_reachability_graph_none_when_no_functions() {
+        let rels = CodeRelationships::default();
+        assert!(generate_reachability_graph(&rels, &HashSet::new()).is_none());
     }
 
     #[test]
-    fn test_inheritance_qualified_type_name() {
+    fn test_generate_type_inheritance_graph_dot_emits_impl_and_super_edges() {
         let code = r#"
-            mod inner {
-                pub struct MyType;
+            trait Base {
+                fn base(&self);
             }
-
-            trait MyTrait {
-                fn test(&self);
+            trait Derived: Base {
+                fn derived(&self);
             }
-
-            impl MyTrait for inner::MyType {
-                fn test(&self) {}
+            struct MyType;
+            impl Derived for MyType {
+                fn derived(&self) {}
             }
         "#;
 
         let rels = parse_and_extract(code);
+        let dot = generate_type_inheritance_graph_dot("MyType", &rels, &HashSet::new()).expect("MyType implements Derived");
 
-        // The type name should include the module path
-        let has_qualified = rels
-            .inheritance
-            .values()
-            .any(|info| info.type_name.contains("inner"));
+        assert!(dot.starts_with("digraph G {"));
+        assert!(dot.contains("\"trait Derived\" -> \"MyType\";"));
+        assert!(dot.contains("\"trait Derived\" -> \"trait Base\";"));
+    }
 
-        assert!(has_qualified);
+    #[test]
+    fn test_generate_type_inheritance_graph_dot_nonexistent_type() {
+        let rels = CodeRelationships::default();
+        assert!(generate_type_inheritance_graph_dot("NonExistent", &rels, &HashSet::new()).is_none());
     }
 }
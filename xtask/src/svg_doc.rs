@@ -0,0 +1,411 @@
+use std::collections::HashMap;
+
+/// A stable handle into a [`Document`]'s node arena. Indices are never reused, so a
+/// `NodeId` obtained from one of the `add_*` methods stays valid for the document's
+/// whole lifetime — the same contract `usvg`'s `svgtree::Document` gives callers that
+/// stash a node id for a later lookup pass.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct NodeId(usize);
+
+/// The drawable content of one arena node. `Root` is always node `0` and carries no
+/// geometry of its own — it exists so `Document::root()` has something to return, in
+/// the style of `svgtree::Document::root()`.
+#[derive(Debug, Clone)]
+pub enum Shape {
+    Root,
+    Rect {
+        x: f64,
+        y: f64,
+        width: f64,
+        height: f64,
+        rx: f64,
+    },
+    Text {
+        x: f64,
+        y: f64,
+        content: String,
+    },
+    /// A directed connection between two graph nodes. Carries both the line
+    /// geometry `to_svg()` needs and the plain-text `from`/`to` labels `to_dot()`
+    /// needs — the two serializers read the same node, just different fields of it.
+    Edge {
+        x1: f64,
+        y1: f64,
+        x2: f64,
+        y2: f64,
+        from_label: String,
+        to_label: String,
+    },
+}
+
+/// One element in a [`Document`]'s arena: its shape plus the same handful of
+/// cross-cutting attributes every SVG element in this codebase's graphs can carry.
+#[derive(Debug, Clone)]
+pub struct NodeData {
+    pub shape: Shape,
+    pub class: Option<String>,
+    pub id: Option<String>,
+    pub href: Option<String>,
+    /// Tooltip text, rendered as an SVG `<title>` child on `to_svg()` (ignored by
+    /// `to_dot()`, which has no equivalent hover affordance).
+    pub title: Option<String>,
+}
+
+/// Arena-backed document for the hand-rolled graphs this crate renders (function
+/// call graphs, type-inheritance graphs, reachability graphs): a flat `Vec<NodeData>`
+/// addressable by [`NodeId`], with a side table for id-based lookup — the same shape
+/// as `usvg`'s `svgtree::Document`, minus the full XML tree `usvg` needs for parsing
+/// arbitrary SVG back in.
+///
+/// Because nodes are addressable, a caller can look one up by id after the graph is
+/// built (to attach a tooltip, or to diff two revisions of the same graph) instead of
+/// re-scanning a formatted string. The same arena also serializes to two independent
+/// backends — `to_svg()` for the rendered graph, `to_dot()` for piping through
+/// Graphviz — rather than hand-building each format separately.
+#[derive(Debug, Clone, Default)]
+pub struct Document {
+    width: u32,
+    height: u32,
+    style: String,
+    defs: String,
+    nodes: Vec<NodeData>,
+    by_id: HashMap<String, NodeId>,
+}
+
+impl Document {
+    pub fn new(width: u32, height: u32) -> Self {
+        Document {
+            width,
+            height,
+            style: String::new(),
+            defs: String::new(),
+            nodes: vec![NodeData {
+                shape: Shape::Root,
+                class: None,
+                id: None,
+                href: None,
+                title: None,
+            }],
+            by_id: HashMap::new(),
+        }
+    }
+
+    /// The document's root node — always `NodeId(0)`, mirroring
+    /// `svgtree::Document::root()`.
+    pub fn root(&self) -> NodeId {
+        NodeId(0)
+    }
+
+    pub fn get(&self, id: NodeId) -> &NodeData {
+        &self.nodes[id.0]
+    }
+
+    pub fn element_by_id(&self, id: &str) -> Option<&NodeData> {
+        self.by_id.get(id).map(|node_id| &self.nodes[node_id.0])
+    }
+
+    /// Raw CSS rules inserted verbatim into the SVG's `<style>` block.
+    pub fn set_style(&mut self, css: impl Into<String>) {
+        self.style = css.into();
+    }
+
+    /// Raw `<defs>` content (e.g. arrowhead `<marker>`s) inserted verbatim.
+    pub fn set_defs(&mut self, defs: impl Into<String>) {
+        self.defs = defs.into();
+    }
+
+    fn push(&mut self, shape: Shape, class: Option<String>, href: Option<String>) -> NodeId {
+        let node_id = NodeId(self.nodes.len());
+        self.nodes.push(NodeData {
+            shape,
+            class,
+            id: None,
+            href,
+            title: None,
+        });
+        node_id
+    }
+
+    /// Attach tooltip text to a previously added node, rendered as an SVG `<title>`
+    /// child on `to_svg()` — the hover-text affordance `usvg`'s `Tree` gets from a
+    /// parsed `<title>` element, but built up programmatically here instead.
+    pub fn set_title(&mut self, id: NodeId, title: impl Into<String>) {
+        self.nodes[id.0].title = Some(title.into());
+    }
+
+    pub fn add_rect(&mut self, x: f64, y: f64, width: f64, height: f64, rx: f64, class: &str) -> NodeId {
+        self.push(Shape::Rect { x, y, width, height, rx }, Some(class.to_string()), None)
+    }
+
+    /// Same as `add_rect`, but registered under `id` for later `element_by_id` lookup
+    /// and wrapped in an `<a href="...">` on render when `href` is given.
+    pub fn add_linked_rect(
+        &mut self,
+        x: f64,
+        y: f64,
+        width: f64,
+        height: f64,
+        rx: f64,
+        class: &str,
+        id: &str,
+        href: Option<&str>,
+    ) -> NodeId {
+        let node_id = self.push(
+            Shape::Rect { x, y, width, height, rx },
+            Some(class.to_string()),
+            href.map(String::from),
+        );
+        self.nodes[node_id.0].id = Some(id.to_string());
+        self.by_id.insert(id.to_string(), node_id);
+        node_id
+    }
+
+    pub fn add_text(&mut self, x: f64, y: f64, content: &str, class: &str) -> NodeId {
+        self.push(
+            Shape::Text { x, y, content: content.to_string() },
+            Some(class.to_string()),
+            None,
+        )
+    }
+
+    pub fn add_linked_text(&mut self, x: f64, y: f64, content: &str, class: &str, href: Option<&str>) -> NodeId {
+        self.push(
+            Shape::Text { x, y, content: content.to_string() },
+            Some(class.to_string()),
+            href.map(String::from),
+        )
+    }
+
+    /// Record a directed edge. `from_label`/`to_label` are the plain display names
+    /// of the two endpoints (used only by `to_dot()`); the coordinates are used only
+    /// by `to_svg()`.
+    pub fn add_edge(
+        &mut self,
+        x1: f64,
+        y1: f64,
+        x2: f64,
+        y2: f64,
+        class: &str,
+        from_label: &str,
+        to_label: &str,
+    ) -> NodeId {
+        self.push(
+            Shape::Edge {
+                x1,
+                y1,
+                x2,
+                y2,
+                from_label: from_label.to_string(),
+                to_label: to_label.to_string(),
+            },
+            Some(class.to_string()),
+            None,
+        )
+    }
+
+    /// Render the arena as SVG, reproducing the hand-written-`format!` output this
+    /// replaces: a `<style>` block, an optional `<defs>` block, then every node in
+    /// insertion order.
+    pub fn to_svg(&self) -> String {
+        let mut out = format!(
+            "<svg width=\"{}\" height=\"{}\" xmlns=\"http://www.w3.org/2000/svg\">\n",
+            self.width, self.height
+        );
+
+        if !self.style.is_empty() {
+            out.push_str(&format!("  <style>\n{}\n  </style>\n", self.style));
+        }
+        if !self.defs.is_empty() {
+            out.push_str(&format!("  <defs>\n{}\n  </defs>\n", self.defs));
+        }
+
+        for node in self.nodes.iter().skip(1) {
+            let class_attr = node
+                .class
+                .as_ref()
+                .map(|c| format!(" class=\"{}\"", c))
+                .unwrap_or_default();
+
+            let title_child = node
+                .title
+                .as_ref()
+                .map(|t| format!("    <title>{}</title>\n", escape_xml(t)));
+
+            let element = match &node.shape {
+                Shape::Root => continue,
+                Shape::Rect { x, y, width, height, rx } => match &title_child {
+                    Some(title) => format!(
+                        "  <rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" rx=\"{}\"{}>\n{}  </rect>\n",
+                        x, y, width, height, rx, class_attr, title
+                    ),
+                    None => format!(
+                        "  <rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" rx=\"{}\"{} />\n",
+                        x, y, width, height, rx, class_attr
+                    ),
+                },
+                Shape::Text { x, y, content } => {
+                    format!("  <text x=\"{}\" y=\"{}\"{}>{}</text>\n", x, y, class_attr, content)
+                }
+                Shape::Edge { x1, y1, x2, y2, .. } => match &title_child {
+                    Some(title) => format!(
+                        "  <line x1=\"{}\" y1=\"{}\" x2=\"{}\" y2=\"{}\"{}>\n{}  </line>\n",
+                        x1, y1, x2, y2, class_attr, title
+                    ),
+                    None => format!(
+                        "  <line x1=\"{}\" y1=\"{}\" x2=\"{}\" y2=\"{}\"{} />\n",
+                        x1, y1, x2, y2, class_attr
+                    ),
+                },
+            };
+
+            match &node.href {
+                Some(href) => out.push_str(&format!("  <a href=\"{}\">\n{}  </a>\n", href, element)),
+                None => out.push_str(&element),
+            }
+        }
+
+        out.push_str("</svg>");
+        out
+    }
+
+    /// Render the arena as Graphviz DOT: every `Edge` node becomes an edge statement
+    /// (quoted on its endpoint labels), with `Rect` nodes contributing a matching
+    /// node declaration so its CSS class survives as a DOT fill color.
+    pub fn to_dot(&self) -> String {
+        let mut out = String::from("digraph G {\n");
+
+        for node in &self.nodes {
+            if let Shape::Rect { .. } = &node.shape {
+                if let Some(label) = self.rect_label(node) {
+                    let fill = dot_fill_for_class(node.class.as_deref());
+                    out.push_str(&format!(
+                        "  \"{}\" [shape=box, style=filled, fillcolor=\"{}\"];\n",
+                        escape_dot_label(&label),
+                        fill
+                    ));
+                }
+            }
+        }
+
+        for node in &self.nodes {
+            if let Shape::Edge { from_label, to_label, .. } = &node.shape {
+                out.push_str(&format!(
+                    "  \"{}\" -> \"{}\";\n",
+                    escape_dot_label(from_label),
+                    escape_dot_label(to_label)
+                ));
+            }
+        }
+
+        out.push_str("}\n");
+        out
+    }
+
+    /// A rect's label, for DOT purposes, is the text of the first `Text` node that
+    /// immediately follows it in the arena — mirroring how every generator in this
+    /// crate pushes a rect and its label text back to back.
+    fn rect_label(&self, rect: &NodeData) -> Option<String> {
+        let rect_index = self.nodes.iter().position(|n| std::ptr::eq(n, rect))?;
+        self.nodes.get(rect_index + 1).and_then(|node| match &node.shape {
+            Shape::Text { content, .. } => Some(content.clone()),
+            _ => None,
+        })
+    }
+}
+
+fn dot_fill_for_class(class: Option<&str>) -> &'static str {
+    match class {
+        Some("current") | Some("type-node") => "#2196f3",
+        Some("caller") => "#ffc107",
+        Some("trait-node") => "#9c27b0",
+        Some("cfg-entry") | Some("cfg-exit") => "#607d8b",
+        _ => "#4caf50",
+    }
+}
+
+fn escape_dot_label(label: &str) -> String {
+    label.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+/// Escape text for use inside SVG element content (a `<title>` body here), the
+/// same five characters the XML spec requires escaping in character data/attributes.
+fn escape_xml(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_root_is_node_zero() {
+        let doc = Document::new(100, 100);
+        assert_eq!(doc.root(), NodeId(0));
+        assert!(matches!(doc.get(doc.root()).shape, Shape::Root));
+    }
+
+    #[test]
+    fn test_element_by_id_finds_linked_rect() {
+        let mut doc = Document::new(100, 100);
+        doc.add_linked_rect(0.0, 0.0, 10.0, 10.0, 2.0, "node", "fn.foo", Some("fn.foo.html"));
+
+        let found = doc.element_by_id("fn.foo").expect("rect registered under id");
+        assert!(matches!(found.shape, Shape::Rect { .. }));
+        assert_eq!(found.href.as_deref(), Some("fn.foo.html"));
+    }
+
+    #[test]
+    fn test_element_by_id_missing_returns_none() {
+        let doc = Document::new(100, 100);
+        assert!(doc.element_by_id("nope").is_none());
+    }
+
+    #[test]
+    fn test_to_svg_wraps_linked_node_in_anchor() {
+        let mut doc = Document::new(100, 100);
+        doc.add_linked_rect(0.0, 0.0, 10.0, 10.0, 2.0, "node", "fn.foo", Some("fn.foo.html"));
+
+        let svg = doc.to_svg();
+        assert!(svg.contains("<svg"));
+        assert!(svg.contains("<a href=\"fn.foo.html\">"));
+        assert!(svg.contains("<rect"));
+    }
+
+    #[test]
+    fn test_to_svg_omits_anchor_for_unlinked_node() {
+        let mut doc = Document::new(100, 100);
+        doc.add_rect(0.0, 0.0, 10.0, 10.0, 2.0, "node");
+
+        let svg = doc.to_svg();
+        assert!(!svg.contains("<a href"));
+    }
+
+    #[test]
+    fn test_to_dot_emits_edge_and_node_declarations() {
+        let mut doc = Document::new(100, 100);
+        doc.add_rect(0.0, 0.0, 10.0, 10.0, 2.0, "current");
+        doc.add_text(5.0, 5.0, "middle", "text");
+        doc.add_rect(100.0, 0.0, 10.0, 10.0, 2.0, "node");
+        doc.add_text(105.0, 5.0, "callee", "text");
+        doc.add_edge(10.0, 5.0, 100.0, 5.0, "edge", "middle", "callee");
+
+        let dot = doc.to_dot();
+        assert!(dot.starts_with("digraph G {"));
+        assert!(dot.contains("\"middle\" -> \"callee\";"));
+        assert!(dot.contains("\"middle\""));
+        assert!(dot.contains("\"callee\""));
+    }
+
+    #[test]
+    fn test_to_dot_escapes_quotes_in_labels() {
+        let mut doc = Document::new(10, 10);
+        doc.add_edge(0.0, 0.0, 1.0, 1.0, "edge", "a\"b", "c");
+
+        let dot = doc.to_dot();
+        assert!(dot.contains("a\\\"b"));
+    }
+}
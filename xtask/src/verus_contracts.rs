@@ -0,0 +1,399 @@
+use std::collections::HashMap;
+
+/// Precondition/postcondition clauses captured from a single `verus!`-verified item.
+///
+/// Each field holds one entry per top-level clause (the comma-separated expressions
+/// that follow a `requires`/`ensures`/`invariant`/`decreases` keyword), rendered as
+/// the source text of the clause rather than a parsed expression — good enough to
+/// display, since Verus's contract syntax isn't valid standalone Rust and `syn` only
+/// ever sees the `verus!{ ... }` invocation as an opaque token tree.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct VerusContract {
+    pub requires: Vec<String>,
+    pub ensures: Vec<String>,
+    pub invariant: Vec<String>,
+    pub decreases: Vec<String>,
+}
+
+impl VerusContract {
+    pub fn is_empty(&self) -> bool {
+        self.requires.is_empty()
+            && self.ensures.is_empty()
+            && self.invariant.is_empty()
+            && self.decreases.is_empty()
+    }
+}
+
+/// Parse every `verus! { ... }` block in `source` and return the contract clauses
+/// for each function/method found inside, keyed the same way as
+/// `CodeRelationships::functions` (`"name"` for free functions, `"Type::name"` for
+/// methods in an `impl` block).
+pub fn extract_verus_contracts(source: &str) -> HashMap<String, VerusContract> {
+    let mut contracts = HashMap::new();
+    let mut search_from = 0;
+
+    while let Some(macro_kw) = find_keyword(source, "verus", search_from) {
+        let Some(brace_offset) = source[macro_kw..].find('{') else {
+            break;
+        };
+        let open = macro_kw + brace_offset;
+        let Some(close) = matching_brace_end(source, open) else {
+            break;
+        };
+
+        scan_items(&source[open + 1..close], None, &mut contracts);
+        search_from = close + 1;
+    }
+
+    contracts
+}
+
+/// Walk `text` (the body of a `verus!` block, or of one `impl` inside it) looking for
+/// `impl` blocks and `fn` items, recursing into the former and recording contracts
+/// for the latter.
+fn scan_items(text: &str, parent_type: Option<&str>, contracts: &mut HashMap<String, VerusContract>) {
+    let mut pos = 0;
+
+    loop {
+        let next_impl = find_keyword(text, "impl", pos);
+        let next_fn = find_keyword(text, "fn", pos);
+
+        pos = match (next_impl, next_fn) {
+            (None, None) => break,
+            (Some(impl_idx), Some(fn_idx)) if impl_idx < fn_idx => {
+                scan_impl_block(text, impl_idx, contracts)
+            }
+            (Some(impl_idx), None) => scan_impl_block(text, impl_idx, contracts),
+            (_, Some(fn_idx)) => scan_fn_item(text, fn_idx, parent_type, contracts),
+        };
+    }
+}
+
+/// Record the type name of an `impl` block starting at `impl_idx` and recurse into
+/// its body. Returns the position just past the block's closing brace.
+fn scan_impl_block(
+    text: &str,
+    impl_idx: usize,
+    contracts: &mut HashMap<String, VerusContract>,
+) -> usize {
+    let after_impl = impl_idx + "impl".len();
+    let type_name = read_ident(text, skip_whitespace(text, after_impl));
+
+    let Some(open) = text[after_impl..].find('{').map(|o| after_impl + o) else {
+        return text.len();
+    };
+    let Some(close) = matching_brace_end(text, open) else {
+        return text.len();
+    };
+
+    if let Some(type_name) = type_name {
+        scan_items(&text[open + 1..close], Some(&type_name), contracts);
+    }
+
+    close + 1
+}
+
+/// Record the contract for the `fn` starting at `fn_idx`, qualifying its name with
+/// `parent_type` when given. Returns the position just past the function's closing
+/// brace.
+fn scan_fn_item(
+    text: &str,
+    fn_idx: usize,
+    parent_type: Option<&str>,
+    contracts: &mut HashMap<String, VerusContract>,
+) -> usize {
+    let after_fn = fn_idx + "fn".len();
+    let Some(fn_name) = read_ident(text, skip_whitespace(text, after_fn)) else {
+        return text.len();
+    };
+
+    let Some(open) = text[after_fn..].find('{').map(|o| after_fn + o) else {
+        return text.len();
+    };
+    let Some(close) = matching_brace_end(text, open) else {
+        return text.len();
+    };
+
+    let signature = &text[fn_idx..open];
+    let body = &text[open + 1..close];
+
+    let contract = VerusContract {
+        requires: extract_clause(signature, "requires", &["ensures"]),
+        ensures: extract_clause(signature, "ensures", &["requires"]),
+        invariant: extract_clause(body, "invariant", &["decreases"]),
+        decreases: extract_clause(body, "decreases", &["invariant"]),
+    };
+
+    if !contract.is_empty() {
+        let qualified_name = match parent_type {
+            Some(parent) => format!("{}::{}", parent, fn_name),
+            None => fn_name,
+        };
+        contracts.insert(qualified_name, contract);
+    }
+
+    close + 1
+}
+
+/// Find the clause introduced by `keyword` and return its comma-separated
+/// expressions. The clause ends at the first of: another clause keyword in
+/// `other_keywords`, an unrelated `{` (e.g. a loop body), or the end of `text`.
+fn extract_clause(text: &str, keyword: &str, other_keywords: &[&str]) -> Vec<String> {
+    let Some(start) = find_keyword(text, keyword, 0) else {
+        return Vec::new();
+    };
+    let clause_start = start + keyword.len();
+
+    let mut end = text.len();
+    for other in other_keywords {
+        if let Some(idx) = find_keyword(text, other, clause_start) {
+            end = end.min(idx);
+        }
+    }
+    if let Some(brace_offset) = text[clause_start..].find('{') {
+        end = end.min(clause_start + brace_offset);
+    }
+
+    split_top_level_commas(&text[clause_start..end])
+}
+
+/// Split `text` on commas that aren't nested inside parens/brackets/braces or a
+/// `|...|` quantifier variable list (e.g. `forall|i: int, j: int| ...`).
+fn split_top_level_commas(text: &str) -> Vec<String> {
+    let mut depth = 0i32;
+    let mut in_pipe = false;
+    let mut current = String::new();
+    let mut clauses = Vec::new();
+
+    let chars: Vec<char> = text.chars().collect();
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        match c {
+            '(' | '[' | '{' => {
+                depth += 1;
+                current.push(c);
+            }
+            ')' | ']' | '}' => {
+                depth -= 1;
+                current.push(c);
+            }
+            '|' if chars.get(i + 1) == Some(&'|') => {
+                // `||` is logical-or, not a quantifier pipe.
+                current.push(c);
+                current.push('|');
+                i += 1;
+            }
+            '|' => {
+                in_pipe = !in_pipe;
+                current.push(c);
+            }
+            ',' if depth == 0 && !in_pipe => {
+                clauses.push(current.trim().to_string());
+                current.clear();
+            }
+            _ => current.push(c),
+        }
+        i += 1;
+    }
+    clauses.push(current.trim().to_string());
+
+    clauses.into_iter().filter(|c| !c.is_empty()).collect()
+}
+
+/// Find the next standalone occurrence of `keyword` in `text` at or after `from`,
+/// i.e. not as a substring of a longer identifier.
+fn find_keyword(text: &str, keyword: &str, from: usize) -> Option<usize> {
+    if from > text.len() {
+        return None;
+    }
+
+    let bytes = text.as_bytes();
+    let mut search_from = from;
+
+    while let Some(relative) = text[search_from..].find(keyword) {
+        let idx = search_from + relative;
+        let before_ok = idx == 0 || !is_ident_byte(bytes[idx - 1]);
+        let after = idx + keyword.len();
+        let after_ok = after >= bytes.len() || !is_ident_byte(bytes[after]);
+
+        if before_ok && after_ok {
+            return Some(idx);
+        }
+        search_from = idx + keyword.len();
+    }
+
+    None
+}
+
+/// Find the `}` that closes the `{` at byte offset `open`, tracking nested
+/// brace depth so an inner block's own braces don't end the scan early.
+fn matching_brace_end(text: &str, open: usize) -> Option<usize> {
+    let mut depth = 0usize;
+
+    for (idx, c) in text[open..].char_indices() {
+        match c {
+            '{' => depth += 1,
+            '}' => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(open + idx);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    None
+}
+
+fn is_ident_byte(b: u8) -> bool {
+    b.is_ascii_alphanumeric() || b == b'_'
+}
+
+fn skip_whitespace(text: &str, from: usize) -> usize {
+    text[from..]
+        .find(|c: char| !c.is_whitespace())
+        .map(|o| from + o)
+        .unwrap_or(text.len())
+}
+
+fn read_ident(text: &str, from: usize) -> Option<String> {
+    let end = text[from..]
+        .find(|c: char| !(c.is_alphanumeric() || c == '_'))
+        .map(|o| from + o)
+        .unwrap_or(text.len());
+
+    if end > from { Some(text[from..end].to_string()) } else { None }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_extract_simple_requires_ensures() {
+        let source = r#"
+            verus! {
+                pub fn sum_two(a: u32, b: u32) -> (result: u64)
+                    requires
+                        a <= 1000000,
+                        b <= 1000000,
+                    ensures
+                        result == (a as u64) + (b as u64),
+                {
+                    (a as u64) + (b as u64)
+                }
+            }
+        "#;
+
+        let contracts = extract_verus_contracts(source);
+        let contract = &contracts["sum_two"];
+
+        assert_eq!(contract.requires.len(), 2);
+        assert!(contract.requires[0].contains("a <= 1000000"));
+        assert_eq!(contract.ensures.len(), 1);
+        assert!(contract.ensures[0].contains("result =="));
+    }
+
+    #[test]
+    fn test_extract_invariant_and_decreases_from_loop() {
+        let source = r#"
+            verus! {
+                pub fn binary_search(arr: &[u32], target: u32) -> (result: Option<usize>)
+                    requires
+                        is_sorted(arr@),
+                {
+                    let mut low: usize = 0;
+                    let mut high: usize = arr.len();
+
+                    while low < high
+                        invariant
+                            low <= high,
+                            high <= arr.len(),
+                        decreases high - low,
+                    {
+                        low = high;
+                    }
+
+                    None
+                }
+            }
+        "#;
+
+        let contracts = extract_verus_contracts(source);
+        let contract = &contracts["binary_search"];
+
+        assert_eq!(contract.requires.len(), 1);
+        assert_eq!(contract.invariant.len(), 2);
+        assert_eq!(contract.decreases.len(), 1);
+        assert!(contract.decreases[0].contains("high - low"));
+    }
+
+    #[test]
+    fn test_extract_method_contract_qualified_by_type() {
+        let source = r#"
+            verus! {
+                pub struct BoundedVec {
+                    pub data: Vec<u32>,
+                }
+
+                impl BoundedVec {
+                    pub fn push(&mut self, value: u32) -> (result: bool)
+                        requires
+                            old(self).inv(),
+                        ensures
+                            result == true,
+                    {
+                        true
+                    }
+                }
+            }
+        "#;
+
+        let contracts = extract_verus_contracts(source);
+
+        assert!(contracts.contains_key("BoundedVec::push"));
+        assert!(!contracts.contains_key("push"));
+    }
+
+    #[test]
+    fn test_quantifier_commas_do_not_split_clause() {
+        let source = r#"
+            verus! {
+                pub fn all_positive(arr: &[i32]) -> (result: bool)
+                    ensures
+                        result == (forall|i: int, j: int| 0 <= i < j < arr.len() ==> true),
+                {
+                    true
+                }
+            }
+        "#;
+
+        let contracts = extract_verus_contracts(source);
+        let contract = &contracts["all_positive"];
+
+        assert_eq!(contract.ensures.len(), 1);
+        assert!(contract.ensures[0].contains("forall|i: int, j: int|"));
+    }
+
+    #[test]
+    fn test_spec_fn_without_contract_is_not_recorded() {
+        let source = r#"
+            verus! {
+                pub open spec fn is_sorted(s: Seq<u32>) -> bool {
+                    forall|i: int, j: int| 0 <= i < j < s.len() ==> s[i] <= s[j]
+                }
+            }
+        "#;
+
+        let contracts = extract_verus_contracts(source);
+        assert!(!contracts.contains_key("is_sorted"));
+    }
+
+    #[test]
+    fn test_no_verus_block_yields_no_contracts() {
+        let source = "pub fn plain(a: u32) -> u32 { a }";
+        assert!(extract_verus_contracts(source).is_empty());
+    }
+}